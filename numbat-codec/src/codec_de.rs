@@ -32,6 +32,13 @@ pub trait Input {
     
     fn flush(&mut self) -> Result<&[u8], DecodeError>;
 
+	/// All remaining bytes, without consuming them.
+	/// Only needed by decoders that find out how many bytes they need while parsing them,
+	/// inputs that cannot provide this return `None`.
+	fn peek_remaining(&mut self) -> Option<&[u8]> {
+		None
+	}
+
 }
 
 impl<'a> Input for &'a [u8] {
@@ -64,6 +71,11 @@ impl<'a> Input for &'a [u8] {
         *self = &[];
         Ok(result)
     }
+
+    #[inline]
+    fn peek_remaining(&mut self) -> Option<&[u8]> {
+        Some(self)
+    }
 }
 
 /// Trait that allows zero-copy read of value-references from slices in LE format.
@@ -96,6 +108,73 @@ pub fn decode_from_byte_slice<D: Decode>(input: &[u8]) -> Result<D, DecodeError>
     D::top_decode(&mut &*input)
 }
 
+/// Trait for types that can be decoded by borrowing directly from the input buffer,
+/// instead of copying data out of it.
+/// 
+/// Any `Decode` type is also `DecodeBorrowed`, so this trait can be used as a bound wherever
+/// both owned and borrowed results should be accepted.
+/// Only byte slices and strings actually borrow from the input.
+pub trait DecodeBorrowed<'a>: Sized {
+    /// Attempt to deserialise the value from the entire input, borrowing from it if possible.
+    fn top_decode_borrowed(input: &'a [u8]) -> Result<Self, DecodeError>;
+
+    /// Attempt to deserialise the value from input,
+    /// using the format of an object nested inside another structure.
+    /// The input slice is advanced past the consumed bytes.
+    fn dep_decode_borrowed(input: &mut &'a [u8]) -> Result<Self, DecodeError>;
+}
+
+impl<'a, T: Decode> DecodeBorrowed<'a> for T {
+    #[inline]
+    fn top_decode_borrowed(input: &'a [u8]) -> Result<Self, DecodeError> {
+        T::top_decode(&mut &*input)
+    }
+
+    #[inline]
+    fn dep_decode_borrowed(input: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        T::dep_decode(input)
+    }
+}
+
+/// Splits the first `length` bytes off the input, keeping the lifetime of the underlying buffer.
+fn read_borrowed_slice<'a>(input: &mut &'a [u8], length: usize) -> Result<&'a [u8], DecodeError> {
+    if length > input.len() {
+        return Err(DecodeError::InputTooShort);
+    }
+    let (result, rest) = input.split_at(length);
+    *input = rest;
+    Ok(result)
+}
+
+impl<'a> DecodeBorrowed<'a> for &'a [u8] {
+    #[inline]
+    fn top_decode_borrowed(input: &'a [u8]) -> Result<Self, DecodeError> {
+        Ok(input)
+    }
+
+    fn dep_decode_borrowed(input: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        let size = usize::dep_decode(input)?;
+        read_borrowed_slice(input, size)
+    }
+}
+
+impl<'a> DecodeBorrowed<'a> for &'a str {
+    fn top_decode_borrowed(input: &'a [u8]) -> Result<Self, DecodeError> {
+        core::str::from_utf8(input).map_err(|_| DecodeError::InvalidValue)
+    }
+
+    fn dep_decode_borrowed(input: &mut &'a [u8]) -> Result<Self, DecodeError> {
+        let bytes = <&'a [u8]>::dep_decode_borrowed(input)?;
+        core::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidValue)
+    }
+}
+
+/// Convenience method, to avoid having to specify type when calling `top_decode_borrowed`.
+#[inline]
+pub fn decode_borrowed_from_byte_slice<'a, D: DecodeBorrowed<'a>>(input: &'a [u8]) -> Result<D, DecodeError> {
+    D::top_decode_borrowed(input)
+}
+
 impl Decode for () {
     const TYPE_INFO: TypeInfo = TypeInfo::Unit;
 
@@ -133,7 +212,8 @@ impl<T: Decode> Decode for Vec<T> {
 			_ => {
                 let mut result: Vec<T> = Vec::new();
                 while input.remaining_len() > 0 {
-                    result.push(T::dep_decode(input)?);
+                    let index = result.len();
+                    result.push(T::dep_decode(input).map_err(|e| e.at_index(index))?);
                 }
                 Ok(result)
 			}
@@ -151,8 +231,8 @@ impl<T: Decode> Decode for Vec<T> {
 			},
 			_ => {
                 let mut result: Vec<T> = Vec::with_capacity(size);
-				for _ in 0..size {
-                    result.push(T::dep_decode(input)?);
+				for index in 0..size {
+                    result.push(T::dep_decode(input).map_err(|e| e.at_index(index))?);
                 }
                 Ok(result)
 			}
@@ -247,18 +327,18 @@ impl<T: Decode> Decode for Option<T> {
         if input.empty() {
             Ok(None)
         } else {
-            let result = Self::dep_decode(input);
+            let result = Self::dep_decode(input)?;
             if input.remaining_len() > 0 {
                 return Err(DecodeError::InputTooLong);
             }
-            result
+            Ok(result)
         }
     }
     
     fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
         match input.read_byte()? {
 			0 => Ok(None),
-			1 => Ok(Some(T::dep_decode(input)?)),
+			1 => Ok(Some(T::dep_decode(input).map_err(|e| e.in_variant("Some"))?)),
 			_ => Err(DecodeError::InvalidValue),
 		}
     }
@@ -274,7 +354,7 @@ macro_rules! tuple_impls {
                 fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
                     let tuple = (
                         $(
-                            $name::dep_decode(input)?,
+                            $name::dep_decode(input).map_err(|e| e.at_index($n))?,
                         )+
                     );
                     Ok(tuple)
@@ -309,8 +389,8 @@ macro_rules! array_impls {
             impl<T: Decode> Decode for [T; $n] {
 				fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
 					let mut r = ArrayVec::new();
-					for _ in 0..$n {
-						r.push(T::dep_decode(input)?);
+					for index in 0..$n {
+						r.push(T::dep_decode(input).map_err(|e| e.at_index(index))?);
 					}
 					let i = r.into_inner();
 
@@ -412,4 +492,63 @@ mod tests {
         let expected: &[u8] = &[/*variant index*/ 0, 0, 0, 3, /*data*/ 0, 0, 0, 1];
         deser_ok(s, expected);
     }
+
+    fn deser_err<V: Decode + Debug>(bytes: &[u8], expected_message: &[u8]) {
+        let err = V::top_decode(&mut &bytes[..]).unwrap_err();
+        let mut message = Vec::new();
+        err.append_message_to(&mut message);
+        assert_eq!(message.as_slice(), expected_message);
+    }
+
+    #[test]
+    fn test_error_path() {
+        deser_err::<Vec<u32>>(&[0, 0, 0, 1, 0, 0, 2], b"[1]: input too short");
+        deser_err::<Vec<Vec<u16>>>(&[0, 0, 0, 0, 0, 0, 0, 1, 0], b"[1][0]: input too short");
+        deser_err::<(u8, bool)>(&[3, 2], b"[1]: invalid value");
+        deser_err::<[bool; 3]>(&[1, 0, 7], b"[2]: invalid value");
+        deser_err::<Option<(u8, u16)>>(&[1, 5, 0], b"Some[1]: input too short");
+        deser_err::<Test>(&[0, 1, 0, 0, 0, 3, 5, 6], b"seq: input too short");
+        deser_err::<Vec<E>>(&[0, 0, 0, 3, 0, 0, 5], b"[0].Struct.a: input too short");
+        deser_err::<E>(&[0, 0, 0, 9], b"invalid value");
+    }
+
+    #[test]
+    fn test_borrowed_bytes() {
+        let input: &[u8] = &[0, 0, 0, 2, 5, 6, 7];
+        let top: &[u8] = decode_borrowed_from_byte_slice(input).unwrap();
+        assert_eq!(top, input);
+        assert_eq!(top.as_ptr(), input.as_ptr());
+
+        let mut nested_input = input;
+        let nested = <&[u8]>::dep_decode_borrowed(&mut nested_input).unwrap();
+        assert_eq!(nested, &[5, 6][..]);
+        assert_eq!(nested.as_ptr(), input[4..].as_ptr());
+        assert_eq!(nested_input, &[7][..]);
+
+        let mut short_input: &[u8] = &[0, 0, 0, 3, 5, 6];
+        assert_eq!(<&[u8]>::dep_decode_borrowed(&mut short_input), Err(DecodeError::InputTooShort));
+    }
+
+    #[test]
+    fn test_borrowed_str() {
+        let s: &str = decode_borrowed_from_byte_slice(b"abc").unwrap();
+        assert_eq!(s, "abc");
+
+        let mut nested_input: &[u8] = &[0, 0, 0, 2, b'h', b'i'];
+        assert_eq!(<&str>::dep_decode_borrowed(&mut nested_input), Ok("hi"));
+        assert!(nested_input.is_empty());
+
+        let invalid: Result<&str, DecodeError> = decode_borrowed_from_byte_slice(&[0xff, 0xfe]);
+        assert_eq!(invalid, Err(DecodeError::InvalidValue));
+    }
+
+    #[test]
+    fn test_borrowed_owned_types() {
+        let n: u32 = decode_borrowed_from_byte_slice(&[1, 0]).unwrap();
+        assert_eq!(n, 256);
+
+        let mut nested_input: &[u8] = &[0, 0, 0, 5, 1];
+        assert_eq!(u32::dep_decode_borrowed(&mut nested_input), Ok(5));
+        assert_eq!(nested_input, &[1][..]);
+    }
 }
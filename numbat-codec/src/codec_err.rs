@@ -1,4 +1,5 @@
 use alloc::vec::Vec;
+use alloc::boxed::Box;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum EncodeError {
@@ -17,6 +18,17 @@ impl EncodeError {
     }
 }
 
+/// One step in the location of a decoding error, inside nested data.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodePathItem {
+    /// Named struct field.
+    Field(&'static str),
+    /// Enum variant, by name.
+    Variant(&'static str),
+    /// Position in a Vec, array or tuple.
+    Index(usize),
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum DecodeError {
     InputTooShort,
@@ -26,9 +38,13 @@ pub enum DecodeError {
     ArrayDecodeErr,
     Static(&'static [u8]),
     Dynamic(Vec<u8>),
+    /// Error that occured while decoding a component of a larger structure.
+    /// The path is ordered from the outermost component inwards.
+    Nested(Vec<DecodePathItem>, Box<DecodeError>),
 }
 
 impl DecodeError {
+    /// Message of the underlying error, without the path.
     pub fn message_bytes(&self) -> &[u8] {
         match self {
             DecodeError::InputTooShort => &b"input too short"[..],
@@ -38,9 +54,123 @@ impl DecodeError {
             DecodeError::ArrayDecodeErr => &b"array decode error"[..],
             DecodeError::Static(msg) => msg,
             DecodeError::Dynamic(msg) => msg.as_slice(),
+            DecodeError::Nested(_, cause) => cause.message_bytes(),
+        }
+    }
+
+    /// Path to the component that failed to decode, empty if the error occured at top level.
+    pub fn path(&self) -> &[DecodePathItem] {
+        match self {
+            DecodeError::Nested(path, _) => path.as_slice(),
+            _ => &[],
+        }
+    }
+
+    /// Marks the error as having occured inside the given component.
+    /// Meant to be called on the way out, so the item is added at the front of the path.
+    pub fn in_path(self, item: DecodePathItem) -> Self {
+        match self {
+            DecodeError::Nested(mut path, cause) => {
+                path.insert(0, item);
+                DecodeError::Nested(path, cause)
+            },
+            other => DecodeError::Nested(alloc::vec![item], Box::new(other)),
         }
     }
+
+    #[inline]
+    pub fn in_field(self, name: &'static str) -> Self {
+        self.in_path(DecodePathItem::Field(name))
+    }
+
+    #[inline]
+    pub fn in_variant(self, name: &'static str) -> Self {
+        self.in_path(DecodePathItem::Variant(name))
+    }
+
+    #[inline]
+    pub fn at_index(self, index: usize) -> Self {
+        self.in_path(DecodePathItem::Index(index))
+    }
+
+    /// Appends the full message to the buffer, including the path if there is one.
+    /// Example: `seq[2].a: input too short`.
+    pub fn append_message_to(&self, dest: &mut Vec<u8>) {
+        let path = self.path();
+        if !path.is_empty() {
+            for (i, item) in path.iter().enumerate() {
+                match item {
+                    DecodePathItem::Field(name) | DecodePathItem::Variant(name) => {
+                        if i > 0 {
+                            dest.push(b'.');
+                        }
+                        dest.extend_from_slice(name.as_bytes());
+                    },
+                    DecodePathItem::Index(index) => {
+                        dest.push(b'[');
+                        append_decimal(*index, dest);
+                        dest.push(b']');
+                    },
+                }
+            }
+            dest.extend_from_slice(&b": "[..]);
+        }
+        dest.extend_from_slice(self.message_bytes());
+    }
 }
 
+/// Writes a number in decimal, for error messages, without pulling in the formatting machinery.
+pub fn append_decimal(mut n: usize, dest: &mut Vec<u8>) {
+    let mut digits = [0u8; 20];
+    let mut num_digits = 0;
+    loop {
+        digits[num_digits] = b'0' + (n % 10) as u8;
+        num_digits += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    for i in (0..num_digits).rev() {
+        dest.push(digits[i]);
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn full_message(err: &DecodeError) -> Vec<u8> {
+        let mut message = Vec::new();
+        err.append_message_to(&mut message);
+        message
+    }
+
+    #[test]
+    fn test_message_no_path() {
+        assert_eq!(full_message(&DecodeError::InputTooShort), b"input too short".to_vec());
+    }
+
+    #[test]
+    fn test_message_with_path() {
+        let err = DecodeError::InvalidValue
+            .in_field("a")
+            .in_variant("Struct")
+            .at_index(12)
+            .in_field("list");
+        assert_eq!(err.message_bytes(), &b"invalid value"[..]);
+        assert_eq!(err.path(), &[
+            DecodePathItem::Field("list"),
+            DecodePathItem::Index(12),
+            DecodePathItem::Variant("Struct"),
+            DecodePathItem::Field("a"),
+        ][..]);
+        assert_eq!(full_message(&err), b"list[12].Struct.a: invalid value".to_vec());
+    }
+
+    #[test]
+    fn test_message_index_first() {
+        let err = DecodeError::InputTooShort.at_index(0).at_index(105);
+        assert_eq!(full_message(&err), b"[105][0]: input too short".to_vec());
+    }
+}
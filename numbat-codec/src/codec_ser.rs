@@ -1,6 +1,11 @@
 use alloc::vec::Vec;
 use crate::codec_err::EncodeError;
 use crate::TypeInfo;
+use arrayvec::{Array, ArrayVec};
+
+/// Values whose nested encoding is known at compile time to fit in this many bytes
+/// are encoded on the stack instead of in a newly allocated Vec.
+pub const MAX_STACK_ENCODED_LEN: usize = 64;
 
 /// Trait that allows writing of data.
 pub trait Output {
@@ -19,9 +24,47 @@ impl Output for Vec<u8> {
 	}
 }
 
+/// Encoding into a fixed-size stack buffer.
+/// Panics if the capacity is exceeded, just like `ArrayVec::push`,
+/// so check `dep_encoded_len` beforehand if the size is not known in advance.
+impl<A: Array<Item = u8>> Output for ArrayVec<A> {
+	fn write(&mut self, bytes: &[u8]) {
+		self.try_extend_from_slice(bytes).expect("encode buffer capacity exceeded")
+	}
+}
+
+/// Output that discards the data and only counts the bytes written.
+/// Used for computing encoded lengths without allocating.
+#[derive(Default)]
+pub struct EncodedLenCounter(pub usize);
+
+impl Output for EncodedLenCounter {
+	#[inline]
+	fn write(&mut self, bytes: &[u8]) {
+		self.0 += bytes.len();
+	}
+}
+
+/// Adds up two fixed encoded lengths. The result is only known if both are known.
+pub const fn add_encoded_len(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+	match (a, b) {
+		(Some(a), Some(b)) => Some(a + b),
+		_ => None,
+	}
+}
+
+/// Fixed encoded length of `n` consecutive items of the same fixed length.
+pub const fn mul_encoded_len(item_len: Option<usize>, n: usize) -> Option<usize> {
+	match item_len {
+		Some(item_len) => Some(item_len * n),
+		None => None,
+	}
+}
+
 /// Trait that allows zero-copy write of value-references to slices in LE format.
 ///
-/// Implementations should override `using_top_encoded` for value types and `dep_encode_to` and `size_hint` for allocating types.
+/// Implementations should override `using_top_encoded` for value types and `dep_encode_to` and `dep_encoded_len` for allocating types.
+/// Types that always encode to the same number of bytes should also set `DEP_ENCODED_LEN`.
 /// Wrapper types should override all methods.
 pub trait Encode: Sized {
 	// !INTERNAL USE ONLY!
@@ -29,6 +72,22 @@ pub trait Encode: Sized {
 	#[doc(hidden)]
 	const TYPE_INFO: TypeInfo = TypeInfo::Unknown;
 
+	/// Length of the nested encoding, if it is the same for all values of the type.
+	/// Allows encoding into a stack buffer instead of allocating.
+	const DEP_ENCODED_LEN: Option<usize> = None;
+
+	/// Number of bytes written by `dep_encode_to`.
+	/// The default implementation performs the encoding without storing the result,
+	/// so types that can compute it cheaply should override it.
+	fn dep_encoded_len(&self) -> usize {
+		if let Some(len) = Self::DEP_ENCODED_LEN {
+			return len;
+		}
+		let mut counter = EncodedLenCounter::default();
+		let _ = self.dep_encode_to(&mut counter);
+		counter.0
+	}
+
 	/// Encode to output, using the format of an object nested inside another structure.
 	/// Does not provide compact version.
 	fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
@@ -49,9 +108,18 @@ pub trait Encode: Sized {
 	/// Do not call for nested objects.
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
 		// default implementation simply use dep_encode_to
-		let mut dest: Vec<u8> = Vec::new();
-		self.dep_encode_to(&mut dest)?;
-		f(dest.as_slice());
+		match Self::DEP_ENCODED_LEN {
+			Some(len) if len <= MAX_STACK_ENCODED_LEN => {
+				let mut dest = ArrayVec::<[u8; MAX_STACK_ENCODED_LEN]>::new();
+				self.dep_encode_to(&mut dest)?;
+				f(dest.as_slice());
+			},
+			_ => {
+				let mut dest: Vec<u8> = Vec::new();
+				self.dep_encode_to(&mut dest)?;
+				f(dest.as_slice());
+			}
+		}
 		Ok(())
 	}
 }
@@ -59,6 +127,7 @@ pub trait Encode: Sized {
 // TODO: consider removing altogether when possible
 impl Encode for () {
 	const TYPE_INFO: TypeInfo = TypeInfo::Unit;
+	const DEP_ENCODED_LEN: Option<usize> = Some(0);
 
 	fn dep_encode_to<O: Output>(&self, _dest: &mut O) -> Result<(), EncodeError> {
 		Ok(())
@@ -76,6 +145,7 @@ impl Encode for () {
 
 impl Encode for u8 {
 	const TYPE_INFO: TypeInfo = TypeInfo::U8;
+	const DEP_ENCODED_LEN: Option<usize> = Some(1);
 
 	fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
 		dest.write(&[*self as u8][..]);
@@ -112,6 +182,10 @@ impl<T: Encode> Encode for &[T] {
 		Ok(())
 	}
 
+	fn dep_encoded_len(&self) -> usize {
+		4 + top_encoded_slice_len(self)
+	}
+
 	#[inline]
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
 		match T::TYPE_INFO {
@@ -121,7 +195,7 @@ impl<T: Encode> Encode for &[T] {
 				f(slice);
 			},
 			_ => {
-				let mut result: Vec<u8> = Vec::new();
+				let mut result: Vec<u8> = Vec::with_capacity(top_encoded_slice_len(self));
 				for x in *self {
 					x.dep_encode_to(&mut result)?;
 				}
@@ -132,7 +206,22 @@ impl<T: Encode> Encode for &[T] {
 	}
 }
 
+/// Length of the slice contents, without the length prefix.
+fn top_encoded_slice_len<T: Encode>(slice: &[T]) -> usize {
+	match T::DEP_ENCODED_LEN {
+		Some(item_len) => item_len * slice.len(),
+		None => slice.iter().map(|x| x.dep_encoded_len()).sum(),
+	}
+}
+
 impl<T: Encode> Encode for &T {
+	const DEP_ENCODED_LEN: Option<usize> = T::DEP_ENCODED_LEN;
+
+	#[inline]
+	fn dep_encoded_len(&self) -> usize {
+		(*self).dep_encoded_len()
+	}
+
 	#[inline]
 	fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
 		(*self).dep_encode_to(dest)
@@ -153,6 +242,11 @@ impl Encode for &str {
 		Ok(())
 	}
 
+	#[inline]
+	fn dep_encoded_len(&self) -> usize {
+		4 + self.len()
+	}
+
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
 		f(self.as_bytes());
 		Ok(())
@@ -165,6 +259,11 @@ impl<T: Encode> Encode for Vec<T> {
 		self.as_slice().dep_encode_to(dest)
 	}
 
+	#[inline]
+	fn dep_encoded_len(&self) -> usize {
+		self.as_slice().dep_encoded_len()
+	}
+
 	#[inline]
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
 		self.as_slice().using_top_encoded(f)
@@ -213,6 +312,7 @@ macro_rules! encode_num {
     ($num_type:ident, $size_in_bits:expr, $signed:expr, $type_info:expr) => {
 		impl Encode for $num_type {
 			const TYPE_INFO: TypeInfo = $type_info;
+			const DEP_ENCODED_LEN: Option<usize> = Some($size_in_bits / 8);
 
 			#[inline]
             fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
@@ -241,6 +341,7 @@ encode_num!{i8, 8, true, TypeInfo::I8}
 
 impl Encode for bool {
 	const TYPE_INFO: TypeInfo = TypeInfo::Bool;
+	const DEP_ENCODED_LEN: Option<usize> = Some(1);
 
 	fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
 		dest.write(&[*self as u8][..]);
@@ -271,15 +372,32 @@ impl<T: Encode> Encode for Option<T> {
 		}
 	}
 
+	fn dep_encoded_len(&self) -> usize {
+		match self {
+			Some(v) => 1 + v.dep_encoded_len(),
+			None => 1,
+		}
+	}
+
 	/// Allow None to be serialized to empty bytes, but leave the leading "1" for Some,
 	/// to allow disambiguation between e.g. Some(0) and None.
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
 		match self {
 			Some(v) => {
-				let mut dest: Vec<u8> = Vec::new();
-				dest.push(1u8);
-				v.dep_encode_to(&mut dest)?;
-				f(dest.as_slice());
+				match T::DEP_ENCODED_LEN {
+					Some(len) if len < MAX_STACK_ENCODED_LEN => {
+						let mut dest = ArrayVec::<[u8; MAX_STACK_ENCODED_LEN]>::new();
+						dest.push(1u8);
+						v.dep_encode_to(&mut dest)?;
+						f(dest.as_slice());
+					},
+					_ => {
+						let mut dest: Vec<u8> = Vec::with_capacity(1 + v.dep_encoded_len());
+						dest.push(1u8);
+						v.dep_encode_to(&mut dest)?;
+						f(dest.as_slice());
+					}
+				}
 			},
 			None => {
 				f(&[]);
@@ -296,6 +414,14 @@ macro_rules! tuple_impls {
             where
                 $($name: Encode,)+
             {
+				const DEP_ENCODED_LEN: Option<usize> = {
+					let mut len = Some(0);
+					$(
+						len = add_encoded_len(len, $name::DEP_ENCODED_LEN);
+					)+
+					len
+				};
+
 				#[inline]
 				fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
 					$(
@@ -303,6 +429,10 @@ macro_rules! tuple_impls {
                     )+
 					Ok(())
 				}
+
+				fn dep_encoded_len(&self) -> usize {
+					0 $( + self.$n.dep_encoded_len() )+
+				}
             }
         )+
     }
@@ -331,6 +461,13 @@ macro_rules! array_impls {
     ($($n: tt,)+) => {
         $(
             impl<T: Encode> Encode for [T; $n] {
+				const DEP_ENCODED_LEN: Option<usize> = mul_encoded_len(T::DEP_ENCODED_LEN, $n);
+
+				#[inline]
+				fn dep_encoded_len(&self) -> usize {
+					top_encoded_slice_len(&self[..])
+				}
+
 				#[inline]
 				fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
 					// the top encoded slice does not serialize its length, so just like the array
@@ -451,6 +588,66 @@ mod tests {
         ser_ok((), &[]);
     }
 
+    fn encoded_len_ok<V: Encode>(element: V) {
+        let mut dest = Vec::new();
+        element.dep_encode_to(&mut dest).unwrap();
+        assert_eq!(element.dep_encoded_len(), dest.len());
+        if let Some(fixed_len) = V::DEP_ENCODED_LEN {
+            assert_eq!(fixed_len, dest.len());
+        }
+    }
+
+    #[test]
+    fn test_dep_encoded_len() {
+        encoded_len_ok(5u8);
+        encoded_len_ok(-5i16);
+        encoded_len_ok(5u32);
+        encoded_len_ok(5usize);
+        encoded_len_ok(5u64);
+        encoded_len_ok(true);
+        encoded_len_ok(());
+        encoded_len_ok("abc");
+        encoded_len_ok([1u8, 2u8, 3u8].to_vec());
+        encoded_len_ok([1i32, 2i32].to_vec());
+        encoded_len_ok(Some(3u16));
+        encoded_len_ok(Option::<u16>::None);
+        encoded_len_ok((7u32, -2i16, true));
+        encoded_len_ok([7u64; 3]);
+        encoded_len_ok(Test { int: 1, seq: [5, 6].to_vec(), another_byte: 7 });
+        encoded_len_ok(E::Tuple(1, 2));
+    }
+
+    #[test]
+    fn test_fixed_encoded_len() {
+        assert_eq!(u64::DEP_ENCODED_LEN, Some(8));
+        assert_eq!(<(u32, i8, bool)>::DEP_ENCODED_LEN, Some(6));
+        assert_eq!(<[u16; 5]>::DEP_ENCODED_LEN, Some(10));
+        assert_eq!(<(u32, Vec<u8>)>::DEP_ENCODED_LEN, None);
+        assert_eq!(Option::<u32>::DEP_ENCODED_LEN, None);
+        assert_eq!(WrappedArray::DEP_ENCODED_LEN, None);
+    }
+
+    #[test]
+    fn test_encode_to_array_vec() {
+        let mut dest = ArrayVec::<[u8; 8]>::new();
+        (7u32, -2i16).dep_encode_to(&mut dest).unwrap();
+        assert_eq!(dest.as_slice(), &[0, 0, 0, 7, 255, 254]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_to_array_vec_overflow() {
+        let mut dest = ArrayVec::<[u8; 4]>::new();
+        let _ = 7u64.dep_encode_to(&mut dest);
+    }
+
+    #[test]
+    fn test_top_encode_fixed_tuple() {
+        ser_ok((1u64, 2u64, 3u32), &[0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 3]);
+        ser_ok(Some((1u8, 2u8)), &[1, 1, 2]);
+        ser_ok([9u64; 10].to_vec(), &[[0, 0, 0, 0, 0, 0, 0, 9]; 10].concat());
+    }
+
     #[test]
     fn test_enum() {
         let u = E::Unit;
@@ -5,11 +5,15 @@ extern crate alloc;
 mod codec_ser;
 mod codec_de;
 mod codec_err;
+mod versioned;
+mod type_description;
 pub mod test_util;
 
 pub use codec_ser::*;
 pub use codec_de::*;
-pub use codec_err::{EncodeError, DecodeError};
+pub use codec_err::{EncodeError, DecodeError, DecodePathItem, append_decimal};
+pub use versioned::*;
+pub use type_description::*;
 
 /// !INTERNAL USE ONLY!
 ///
@@ -58,9 +62,9 @@ pub mod test_struct {
     impl Decode for Test {
         fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
             Ok(Test{
-                int: u16::dep_decode(input)?,
-                seq: Vec::<u8>::dep_decode(input)?,
-                another_byte: u8::dep_decode(input)?,
+                int: u16::dep_decode(input).map_err(|e| e.in_field("int"))?,
+                seq: Vec::<u8>::dep_decode(input).map_err(|e| e.in_field("seq"))?,
+                another_byte: u8::dep_decode(input).map_err(|e| e.in_field("another_byte"))?,
             })
         }
     }
@@ -101,14 +105,46 @@ pub mod test_struct {
         fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
             match u32::dep_decode(input)? {
                 0 => Ok(E::Unit),
-                1 => Ok(E::Newtype(u32::dep_decode(input)?)),
-                2 => Ok(E::Tuple(u32::dep_decode(input)?, u32::dep_decode(input)?)),
-                3 => Ok(E::Struct{ a: u32::dep_decode(input)? }),
+                1 => Ok(E::Newtype(u32::dep_decode(input).map_err(|e| e.at_index(0).in_variant("Newtype"))?)),
+                2 => Ok(E::Tuple(
+                    u32::dep_decode(input).map_err(|e| e.at_index(0).in_variant("Tuple"))?,
+                    u32::dep_decode(input).map_err(|e| e.at_index(1).in_variant("Tuple"))?)),
+                3 => Ok(E::Struct{ a: u32::dep_decode(input).map_err(|e| e.in_field("a").in_variant("Struct"))? }),
                 _ => Err(DecodeError::InvalidValue),
             }
         }
     }
 
+    impl TypeDescription for Test {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::Struct {
+                name: "Test",
+                fields: alloc::vec![
+                    FieldDescriptor::new::<u16>("int"),
+                    FieldDescriptor::new::<Vec<u8>>("seq"),
+                    FieldDescriptor::new::<u8>("another_byte"),
+                ],
+            }
+        }
+    }
+
+    impl TypeDescription for E {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::Enum {
+                name: "E",
+                variants: alloc::vec![
+                    VariantDescriptor { name: "Unit", fields: Vec::new() },
+                    VariantDescriptor { name: "Newtype", fields: alloc::vec![FieldDescriptor::new::<u32>("0")] },
+                    VariantDescriptor { name: "Tuple", fields: alloc::vec![
+                        FieldDescriptor::new::<u32>("0"),
+                        FieldDescriptor::new::<u32>("1"),
+                    ] },
+                    VariantDescriptor { name: "Struct", fields: alloc::vec![FieldDescriptor::new::<u32>("a")] },
+                ],
+            }
+        }
+    }
+
     #[derive(PartialEq, Debug, Clone, Copy)]
     pub struct WrappedArray(pub [u8; 5]);
 
@@ -0,0 +1,242 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Describes the shape of an encodable type.
+/// Meant for tooling that needs to interpret encoded data without access to the Rust types,
+/// such as ABI exporters or storage inspectors.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TypeDescriptor {
+    Unit,
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    BigUint,
+    BigInt,
+    /// UTF-8 string, encoded like a list of bytes.
+    Str,
+    Option(Box<TypeDescriptor>),
+    /// Variable length sequence, length-prefixed when nested.
+    List(Box<TypeDescriptor>),
+    /// Fixed length sequence, never length-prefixed.
+    Array(Box<TypeDescriptor>, usize),
+    Tuple(Vec<TypeDescriptor>),
+    Struct {
+        name: &'static str,
+        fields: Vec<FieldDescriptor>,
+    },
+    /// Variants are encoded as a u32 index, followed by the variant fields.
+    Enum {
+        name: &'static str,
+        variants: Vec<VariantDescriptor>,
+    },
+    /// Endpoint argument or result spanning a variable number of arguments or results.
+    MultiValue(Box<TypeDescriptor>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldDescriptor {
+    pub name: &'static str,
+    pub type_descriptor: TypeDescriptor,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VariantDescriptor {
+    pub name: &'static str,
+    /// Unnamed fields of tuple variants get their index as name.
+    pub fields: Vec<FieldDescriptor>,
+}
+
+impl FieldDescriptor {
+    #[inline]
+    pub fn new<T: TypeDescription>(name: &'static str) -> Self {
+        FieldDescriptor {
+            name,
+            type_descriptor: T::type_descriptor(),
+        }
+    }
+}
+
+/// Provides runtime information about the shape of a type.
+/// Should be implemented for all types implementing `Encode` or `Decode`.
+pub trait TypeDescription {
+    fn type_descriptor() -> TypeDescriptor;
+}
+
+macro_rules! simple_descriptions {
+    ($($t:ty => $descriptor:ident,)+) => {
+        $(
+            impl TypeDescription for $t {
+                #[inline]
+                fn type_descriptor() -> TypeDescriptor {
+                    TypeDescriptor::$descriptor
+                }
+            }
+        )+
+    }
+}
+
+// usize and isize are encoded on 32 bits
+simple_descriptions! {
+    () => Unit,
+    bool => Bool,
+    u8 => U8,
+    i8 => I8,
+    u16 => U16,
+    i16 => I16,
+    u32 => U32,
+    i32 => I32,
+    usize => U32,
+    isize => I32,
+    u64 => U64,
+    i64 => I64,
+    &str => Str,
+}
+
+impl<T: TypeDescription> TypeDescription for &T {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        T::type_descriptor()
+    }
+}
+
+impl<T: TypeDescription> TypeDescription for &[T] {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::List(Box::new(T::type_descriptor()))
+    }
+}
+
+impl<T: TypeDescription> TypeDescription for Vec<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::List(Box::new(T::type_descriptor()))
+    }
+}
+
+impl<T: TypeDescription> TypeDescription for Option<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Option(Box::new(T::type_descriptor()))
+    }
+}
+
+macro_rules! tuple_impls {
+    ($(($($name:ident)+))+) => {
+        $(
+            impl<$($name),+> TypeDescription for ($($name,)+)
+            where
+                $($name: TypeDescription,)+
+            {
+                fn type_descriptor() -> TypeDescriptor {
+                    TypeDescriptor::Tuple(alloc::vec![$($name::type_descriptor()),+])
+                }
+            }
+        )+
+    }
+}
+
+tuple_impls! {
+    (T0)
+    (T0 T1)
+    (T0 T1 T2)
+    (T0 T1 T2 T3)
+    (T0 T1 T2 T3 T4)
+    (T0 T1 T2 T3 T4 T5)
+    (T0 T1 T2 T3 T4 T5 T6)
+    (T0 T1 T2 T3 T4 T5 T6 T7)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
+}
+
+macro_rules! array_impls {
+    ($($n: tt,)+) => {
+        $(
+            impl<T: TypeDescription> TypeDescription for [T; $n] {
+                fn type_descriptor() -> TypeDescriptor {
+                    TypeDescriptor::Array(Box::new(T::type_descriptor()), $n)
+                }
+            }
+        )+
+    }
+}
+
+array_impls!(
+	1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+	17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+	32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51,
+	52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71,
+	72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91,
+	92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108,
+	109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124,
+	125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140,
+	141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156,
+	157, 158, 159, 160, 161, 162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172,
+	173, 174, 175, 176, 177, 178, 179, 180, 181, 182, 183, 184, 185, 186, 187, 188,
+	189, 190, 191, 192, 193, 194, 195, 196, 197, 198, 199, 200, 201, 202, 203, 204,
+	205, 206, 207, 208, 209, 210, 211, 212, 213, 214, 215, 216, 217, 218, 219, 220,
+	221, 222, 223, 224, 225, 226, 227, 228, 229, 230, 231, 232, 233, 234, 235, 236,
+	237, 238, 239, 240, 241, 242, 243, 244, 245, 246, 247, 248, 249, 250, 251, 252,
+	253, 254, 255, 256, 384, 512, 768, 1024, 2048, 4096, 8192, 16384, 32768,
+);
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_struct::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(u8::type_descriptor(), TypeDescriptor::U8);
+        assert_eq!(usize::type_descriptor(), TypeDescriptor::U32);
+        assert_eq!(<&i64>::type_descriptor(), TypeDescriptor::I64);
+        assert_eq!(<&str>::type_descriptor(), TypeDescriptor::Str);
+    }
+
+    #[test]
+    fn test_collections() {
+        assert_eq!(Vec::<Option<bool>>::type_descriptor(),
+            TypeDescriptor::List(Box::new(TypeDescriptor::Option(Box::new(TypeDescriptor::Bool)))));
+        assert_eq!(<[u16; 3]>::type_descriptor(),
+            TypeDescriptor::Array(Box::new(TypeDescriptor::U16), 3));
+        assert_eq!(<(u8, &[i32])>::type_descriptor(),
+            TypeDescriptor::Tuple(vec![TypeDescriptor::U8, TypeDescriptor::List(Box::new(TypeDescriptor::I32))]));
+    }
+
+    #[test]
+    fn test_struct() {
+        assert_eq!(Test::type_descriptor(), TypeDescriptor::Struct {
+            name: "Test",
+            fields: vec![
+                FieldDescriptor { name: "int", type_descriptor: TypeDescriptor::U16 },
+                FieldDescriptor { name: "seq", type_descriptor: TypeDescriptor::List(Box::new(TypeDescriptor::U8)) },
+                FieldDescriptor { name: "another_byte", type_descriptor: TypeDescriptor::U8 },
+            ],
+        });
+    }
+
+    #[test]
+    fn test_enum() {
+        match E::type_descriptor() {
+            TypeDescriptor::Enum { name, variants } => {
+                assert_eq!(name, "E");
+                assert_eq!(variants.len(), 4);
+                assert_eq!(variants[0], VariantDescriptor { name: "Unit", fields: Vec::new() });
+                assert_eq!(variants[2].fields[1], FieldDescriptor::new::<u32>("1"));
+                assert_eq!(variants[3].fields[0].name, "a");
+            },
+            _ => panic!("enum descriptor expected"),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::codec_de::*;
+use crate::codec_ser::*;
+use crate::codec_err::{EncodeError, DecodeError};
+use crate::type_description::*;
+
+/// Error returned when the stored version tag is newer than the one known by the type.
+pub const UNKNOWN_VERSION: &[u8] = b"unknown encoding version";
+
+/// Types whose layout can change over time, while still being able to decode data written by older versions.
+///
+/// Whenever the layout changes, increment `VERSION` and keep handling the older versions in `dep_decode_version`.
+/// Use together with `VersionTagged`, which writes the version in front of the data.
+pub trait Versioned: Encode + Sized {
+    /// Version of the current layout, the one produced by `dep_encode_to`.
+    const VERSION: u8;
+
+    /// Decodes data written by the given version of the type.
+    /// The version is never greater than `VERSION`.
+    fn dep_decode_version<I: Input>(version: u8, input: &mut I) -> Result<Self, DecodeError>;
+}
+
+/// Wrapper that prefixes the encoding of a `Versioned` type with its version, as a single byte.
+/// Decoding picks the layout based on that byte.
+///
+/// The tag is written in both top and nested encodings, so the wrapper can be stored directly,
+/// or used for fields of other types.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct VersionTagged<T>(pub T);
+
+impl<T> VersionTagged<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for VersionTagged<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        VersionTagged(value)
+    }
+}
+
+impl<T: Versioned> Encode for VersionTagged<T> {
+    const DEP_ENCODED_LEN: Option<usize> = add_encoded_len(Some(1), T::DEP_ENCODED_LEN);
+
+    fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+        dest.push_byte(T::VERSION);
+        self.0.dep_encode_to(dest)
+    }
+
+    #[inline]
+    fn dep_encoded_len(&self) -> usize {
+        1 + self.0.dep_encoded_len()
+    }
+}
+
+impl<T: Versioned> Decode for VersionTagged<T> {
+    fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+        let version = input.read_byte()?;
+        if version > T::VERSION {
+            return Err(DecodeError::Static(UNKNOWN_VERSION));
+        }
+        Ok(VersionTagged(T::dep_decode_version(version, input)?))
+    }
+}
+
+/// Described as the version byte followed by the value.
+impl<T: TypeDescription> TypeDescription for VersionTagged<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        <(u8, T)>::type_descriptor()
+    }
+}
+
+/// Decodes a field that was appended to a type after it was first stored.
+/// If the input is exhausted, which is the case for data written before the field existed,
+/// the field gets its default value.
+///
+/// Only makes sense for trailing fields of a top-level encoded value, such as a value in storage,
+/// since nested values are followed by other data.
+pub fn dep_decode_or_default<T, I>(input: &mut I) -> Result<T, DecodeError>
+where
+    T: Decode + Default,
+    I: Input,
+{
+    if input.empty() {
+        Ok(T::default())
+    } else {
+        T::dep_decode(input)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::ser_deser_ok;
+    use alloc::vec::Vec;
+
+    /// Version 0 only had the `amount` field, version 1 added `fee`.
+    #[derive(PartialEq, Debug)]
+    struct Deposit {
+        amount: u32,
+        fee: u16,
+    }
+
+    impl Encode for Deposit {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+            self.amount.dep_encode_to(dest)?;
+            self.fee.dep_encode_to(dest)
+        }
+    }
+
+    impl Versioned for Deposit {
+        const VERSION: u8 = 1;
+
+        fn dep_decode_version<I: Input>(version: u8, input: &mut I) -> Result<Self, DecodeError> {
+            let amount = u32::dep_decode(input).map_err(|e| e.in_field("amount"))?;
+            let fee = if version >= 1 {
+                u16::dep_decode(input).map_err(|e| e.in_field("fee"))?
+            } else {
+                0
+            };
+            Ok(Deposit { amount, fee })
+        }
+    }
+
+    /// Same evolution as `Deposit`, but relying on the missing trailing bytes instead of a version tag.
+    #[derive(PartialEq, Debug)]
+    struct Account {
+        balance: u32,
+        nonce: u64,
+    }
+
+    impl Encode for Account {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+            self.balance.dep_encode_to(dest)?;
+            self.nonce.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for Account {
+        fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+            Ok(Account {
+                balance: u32::dep_decode(input).map_err(|e| e.in_field("balance"))?,
+                nonce: dep_decode_or_default(input).map_err(|e| e.in_field("nonce"))?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_version_tagged_current() {
+        let deposit = VersionTagged(Deposit { amount: 5, fee: 1 });
+        ser_deser_ok(deposit, &[1, 0, 0, 0, 5, 0, 1]);
+        assert_eq!(VersionTagged::<Deposit>::DEP_ENCODED_LEN, None);
+    }
+
+    #[test]
+    fn test_version_tagged_old() {
+        let decoded = VersionTagged::<Deposit>::top_decode(&mut &[0u8, 0, 0, 0, 5][..]).unwrap();
+        assert_eq!(decoded.into_inner(), Deposit { amount: 5, fee: 0 });
+    }
+
+    #[test]
+    fn test_version_tagged_unknown() {
+        let result = VersionTagged::<Deposit>::top_decode(&mut &[2u8, 0, 0, 0, 5, 0, 1][..]);
+        assert_eq!(result, Err(DecodeError::Static(UNKNOWN_VERSION)));
+    }
+
+    #[test]
+    fn test_version_tagged_nested() {
+        let list: Vec<VersionTagged<Deposit>> = alloc::vec![
+            VersionTagged(Deposit { amount: 1, fee: 2 }),
+            VersionTagged(Deposit { amount: 3, fee: 4 }),
+        ];
+        ser_deser_ok(list, &[1, 0, 0, 0, 1, 0, 2, 1, 0, 0, 0, 3, 0, 4]);
+    }
+
+    #[test]
+    fn test_trailing_field_default() {
+        ser_deser_ok(Account { balance: 7, nonce: 3 }, &[0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 3]);
+
+        let old_layout = Account { balance: 7, nonce: 0 };
+        let decoded = Account::top_decode(&mut &[0u8, 0, 0, 7][..]).unwrap();
+        assert_eq!(decoded, old_layout);
+
+        let truncated = Account::top_decode(&mut &[0u8, 0, 0, 7, 0, 0][..]);
+        assert_eq!(truncated.unwrap_err().path(), &[crate::DecodePathItem::Field("nonce")][..]);
+    }
+}
@@ -2,6 +2,7 @@
 use crate::big_uint_mock::*;
 
 use num_traits::sign::Signed;
+use num_traits::cast::ToPrimitive;
 use core::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use core::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
 
@@ -160,6 +161,12 @@ impl Encode for RustBigInt {
     }
 }
 
+impl TypeDescription for RustBigInt {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::BigInt
+    }
+}
+
 impl Decode for RustBigInt {
     const TYPE_INFO: TypeInfo = TypeInfo::BigInt;
 
@@ -197,6 +204,29 @@ impl numbat_wasm::BigIntApi<RustBigUint> for RustBigInt {
         let bi = BigInt::from_signed_bytes_be(bytes);
         bi.into()
     }
+
+    fn pow(&self, exp: u32) -> Self {
+        RustBigInt(num_traits::pow::Pow::pow(&self.0, exp))
+    }
+
+    fn sqrt(&self) -> Self {
+        if self.0.is_negative() {
+            panic!("Cannot take the square root of a negative number")
+        }
+        RustBigInt(self.0.sqrt())
+    }
+
+    fn log2(&self) -> Option<u32> {
+        if self.0.is_positive() {
+            Some(self.0.bits() as u32 - 1)
+        } else {
+            None
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
 }
 
 impl RustBigInt {
@@ -204,3 +234,44 @@ impl RustBigInt {
         self.0.to_signed_bytes_be()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numbat_wasm::Rounding;
+
+    fn big(value: i64) -> RustBigInt {
+        RustBigInt(BigInt::from(value))
+    }
+
+    #[test]
+    fn test_pow_sqrt_log2() {
+        assert_eq!(big(-2).pow(3), big(-8));
+        assert_eq!(big(17).sqrt(), big(4));
+        assert_eq!(big(8).log2(), Some(3));
+        assert_eq!(big(0).log2(), None);
+        assert_eq!(big(-8).log2(), None);
+    }
+
+    #[test]
+    fn test_to_i64() {
+        assert_eq!(big(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(RustBigInt(BigInt::from(i64::MAX) + 1).to_i64(), None);
+    }
+
+    #[test]
+    fn test_mul_div() {
+        // -7.5
+        assert_eq!(big(-5).mul_div(&big(3), &big(2), Rounding::Down), big(-8));
+        assert_eq!(big(-5).mul_div(&big(3), &big(2), Rounding::Up), big(-7));
+        assert_eq!(big(-5).mul_div(&big(3), &big(2), Rounding::Nearest), big(-8));
+        assert_eq!(big(5).mul_div(&big(3), &big(-2), Rounding::Down), big(-8));
+        // 7.5
+        assert_eq!(big(-5).mul_div(&big(3), &big(-2), Rounding::Down), big(7));
+        assert_eq!(big(-5).mul_div(&big(3), &big(-2), Rounding::Up), big(8));
+        assert_eq!(big(5).mul_div(&big(3), &big(2), Rounding::Nearest), big(8));
+        // -4.2857...
+        assert_eq!(big(-10).mul_div(&big(3), &big(7), Rounding::Nearest), big(-4));
+        assert_eq!(big(-10).mul_div(&big(3), &big(5), Rounding::Down), big(-6));
+    }
+}
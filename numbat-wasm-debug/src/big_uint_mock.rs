@@ -9,6 +9,7 @@ use alloc::vec::Vec;
 use numbat_wasm::BigUintApi;
 
 use num_bigint::BigInt;
+use num_traits::cast::ToPrimitive;
 use core::cmp::Ordering;
 
 #[derive(Debug)]
@@ -238,6 +239,12 @@ impl Encode for RustBigUint {
     }
 }
 
+impl TypeDescription for RustBigUint {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::BigUint
+    }
+}
+
 impl Decode for RustBigUint {
     const TYPE_INFO: TypeInfo = TypeInfo::BigUint;
     
@@ -290,4 +297,84 @@ impl numbat_wasm::BigUintApi for RustBigUint {
         let bi = BigInt::from_bytes_be(num_bigint::Sign::Plus, bytes);
         bi.into()
     }
+
+    fn pow(&self, exp: u32) -> Self {
+        RustBigUint(num_traits::pow::Pow::pow(&self.0, exp))
+    }
+
+    fn sqrt(&self) -> Self {
+        RustBigUint(self.0.sqrt())
+    }
+
+    fn log2(&self) -> Option<u32> {
+        if self.0.sign() == num_bigint::Sign::NoSign {
+            None
+        } else {
+            Some(self.0.bits() as u32 - 1)
+        }
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let result = &self.0 - &other.0;
+        if result.sign() == num_bigint::Sign::Minus {
+            None
+        } else {
+            Some(RustBigUint(result))
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numbat_wasm::Rounding;
+
+    fn big(value: u64) -> RustBigUint {
+        RustBigUint(BigInt::from(value))
+    }
+
+    #[test]
+    fn test_pow_sqrt_log2() {
+        assert_eq!(big(3).pow(4), big(81));
+        assert_eq!(big(3).pow(0), big(1));
+        assert_eq!(big(80).sqrt(), big(8));
+        assert_eq!(big(81).sqrt(), big(9));
+        assert_eq!(big(0).sqrt(), big(0));
+        assert_eq!(big(1).log2(), Some(0));
+        assert_eq!(big(1023).log2(), Some(9));
+        assert_eq!(big(1024).log2(), Some(10));
+        assert_eq!(big(0).log2(), None);
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        assert_eq!(big(5).checked_sub(&big(3)), Some(big(2)));
+        assert_eq!(big(5).checked_sub(&big(5)), Some(big(0)));
+        assert_eq!(big(3).checked_sub(&big(5)), None);
+    }
+
+    #[test]
+    fn test_to_u64_min_max() {
+        assert_eq!(big(u64::MAX).to_u64(), Some(u64::MAX));
+        let too_big = RustBigUint(BigInt::from(u64::MAX) + 1);
+        assert_eq!(too_big.to_u64(), None);
+        assert_eq!(big(3).min(big(5)), big(3));
+        assert_eq!(big(3).max(big(5)), big(5));
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(big(10).mul_div(&big(3), &big(4), Rounding::Down), big(7));
+        assert_eq!(big(10).mul_div(&big(3), &big(4), Rounding::Up), big(8));
+        assert_eq!(big(10).mul_div(&big(3), &big(4), Rounding::Nearest), big(8));
+        assert_eq!(big(10).mul_div(&big(3), &big(7), Rounding::Nearest), big(4));
+        assert_eq!(big(10).mul_div(&big(3), &big(5), Rounding::Up), big(6));
+        // the intermediate product does not overflow
+        let large = big(u64::MAX);
+        assert_eq!(large.mul_div(&large, &large, Rounding::Down), large);
+    }
 }
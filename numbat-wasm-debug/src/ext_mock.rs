@@ -9,6 +9,8 @@ use numbat_wasm::ContractHookApi;
 use numbat_wasm::CallableContract;
 use numbat_wasm::BigUintApi;
 use numbat_wasm::err_msg;
+use numbat_wasm::call_data::{BuiltinFunctionCall, CallDataSerializer};
+use numbat_wasm::AsynCallArg;
 
 use num_bigint::{BigInt};
 use num_traits::cast::ToPrimitive;
@@ -29,6 +31,11 @@ const ADDRESS_LENGTH: usize = 32;
 const KEY_LENGTH: usize = 32;
 const TOPIC_LENGTH: usize = 32;
 
+/// Status of transactions where the contract signalled an error.
+const USER_ERROR_STATUS: i32 = 4;
+
+const INSUFFICIENT_FUNDS: &[u8] = b"insufficient funds";
+
 fn address_hex(address: &H256) -> alloc::string::String {
     alloc::format!("0x{}", hex::encode(address.as_bytes()))
 }
@@ -42,7 +49,27 @@ pub struct AccountData {
     pub nonce: u64,
     pub balance: BigInt,
     pub storage: HashMap<Vec<u8>, Vec<u8>>,
+    pub dct_balances: HashMap<Vec<u8>, BigInt>,
+    pub username: Vec<u8>,
     pub contract: Option<Box<dyn CallableContract>>,
+    /// Account that deployed the contract.
+    pub contract_owner: Option<Address>,
+}
+
+impl AccountData {
+    /// Account with no balance, storage or contract.
+    pub fn new(address: Address) -> Self {
+        AccountData {
+            address,
+            nonce: 0,
+            balance: 0.into(),
+            storage: HashMap::new(),
+            dct_balances: HashMap::new(),
+            username: Vec::new(),
+            contract: None,
+            contract_owner: None,
+        }
+    }
 }
 
 impl fmt::Display for AccountData {
@@ -69,6 +96,10 @@ pub struct TxData {
     call_value: BigInt,
     from: Address,
     to: Address,
+    tx_hash: H256,
+    callback_call_index: u32,
+    /// Deploys get their address from the deployer and their nonce, unless one is given.
+    derive_new_address: bool,
 }
 
 impl fmt::Display for TxData {
@@ -91,9 +122,20 @@ impl TxData {
             call_value: 0.into(),
             from,
             to,
+            tx_hash: H256::zero(),
+            callback_call_index: 0,
+            derive_new_address: false,
         }
     }
 
+    /// Deploys the contract at an address derived from the deployer's address and nonce, see `new_contract_address`.
+    /// The new address is returned in the result.
+    pub fn new_deploy(new_contract: Box<dyn CallableContract>, from: Address) -> Self {
+        let mut tx = TxData::new_create(new_contract, from, Address::zero());
+        tx.derive_new_address = true;
+        tx
+    }
+
     pub fn new_call(func_name: &'static str, from: Address, to: Address) -> Self {
         TxData{
             func_name,
@@ -102,24 +144,76 @@ impl TxData {
             call_value: 0.into(),
             from,
             to,
+            tx_hash: H256::zero(),
+            callback_call_index: 0,
+            derive_new_address: false,
         }
     }
 
+    /// Plain value transfer, with no function called.
+    /// Contracts pass it to their fallback, if they have one, or reject it.
+    pub fn new_transfer(from: Address, to: Address, amount: BigInt) -> Self {
+        let mut tx = TxData::new_call("", from, to);
+        tx.set_call_value(amount);
+        tx
+    }
+
+    pub fn set_call_value(&mut self, call_value: BigInt) {
+        self.call_value = call_value;
+    }
+
     pub fn add_arg(&mut self, arg: Vec<u8>) {
         self.args.push(arg);
     }
+
+    /// Adds an argument of any type contracts accept, encoded the way they expect it.
+    /// Multi-value arguments, like `VarArgs`, are added as several arguments.
+    pub fn add_typed_arg<T: AsynCallArg>(&mut self, arg: &T) {
+        let mut call_data_ser = CallDataSerializer::new(&[]);
+        if let Err(sc_err) = arg.push_async_arg(&mut call_data_ser) {
+            panic!("cannot encode argument: {}", String::from_utf8_lossy(sc_err.as_bytes()));
+        }
+        let args = numbat_wasm::call_data_args(call_data_ser.as_slice()).unwrap();
+        self.args.extend(args);
+    }
+
+    pub fn set_tx_hash(&mut self, tx_hash: H256) {
+        self.tx_hash = tx_hash;
+    }
+}
+
+/// Async call made by a contract, recorded by the mock instead of being executed.
+/// Built-in function calls are the exception: they are executed right away, and also recorded.
+/// A DCT transfer that also calls the receiving contract only executes the transfer,
+/// the test can run the call from the recorded data.
+/// Every async call of a transaction gets its own index, including built-in function calls.
+#[derive(Clone, Debug)]
+pub struct PendingAsyncCall {
+    pub from: Address,
+    pub to: Address,
+    pub amount: BigInt,
+    pub gas_limit: Option<u64>,
+    pub data: Vec<u8>,
+    pub tx_hash: H256,
+    pub call_index: u32,
 }
 
 #[derive(Clone)]
 pub struct TxResult {
     pub result_status: i32,
+    pub result_message: Vec<u8>,
     pub result_values: Vec<Vec<u8>>,
+    /// Address of the contract deployed by the transaction, if any.
+    pub new_address: Option<Address>,
 }
 
 impl fmt::Display for TxResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let results_hex: Vec<String> = self.result_values.iter().map(|r| format!("0x{}", hex::encode(r))).collect();
-        write!(f, "TxResult {{\n\tresult_status: {},\n\tresult_values:{:?}\n}}", self.result_status, results_hex)
+        write!(f, "TxResult {{\n\tresult_status: {},\n\tresult_message: {},\n\tresult_values:{:?}\n}}",
+            self.result_status,
+            String::from_utf8_lossy(self.result_message.as_slice()),
+            results_hex)
     }
 }
 
@@ -127,7 +221,9 @@ impl TxResult {
     pub fn empty() -> TxResult {
         TxResult {
             result_status: 0,
+            result_message: Vec::new(),
             result_values: Vec::new(),
+            new_address: None,
         }
     }
     pub fn print(&self) {
@@ -139,6 +235,8 @@ pub struct AndesMockState {
     current_tx: Option<TxData>,
     current_result: TxResult,
     accounts: HashMap<Address, AccountData>,
+    async_calls: Vec<PendingAsyncCall>,
+    num_async_calls_in_tx: u32,
 }
 
 pub struct AndesMockRef {
@@ -157,29 +255,74 @@ impl AndesMockState {
             current_tx: None,
             current_result: TxResult::empty(),
             accounts: HashMap::new(),
+            async_calls: Vec::new(),
+            num_async_calls_in_tx: 0,
         };
         let state_ref = Rc::new(RefCell::new(state));
         AndesMockRef{ state_ref }
     }
 
-    fn create_account_if_necessary(&mut self, tx: &TxData) {
-        if let Some(ref tx_contract) = tx.new_contract {
-            if self.accounts.contains_key(&tx.to) {
-                panic!("Account already exists");
-            }
-            self.accounts.insert(tx.to.clone(), AccountData{
-                address: tx.to.clone(),
-                nonce: 0,
-                balance: 0.into(),
-                storage: HashMap::new(),
-                contract: Some(tx_contract.clone_contract()),
-            });
+    /// Mock with a single empty account, which is also the current contract.
+    /// To be used for writing small tests, that call the API directly.
+    pub fn new_ref_with_account(address: &Address) -> AndesMockRef {
+        let mock_ref = Self::new_ref();
+        mock_ref.add_account(AccountData::new(address.clone()));
+        mock_ref.set_dummy_tx(address);
+        mock_ref
+    }
+
+    /// Deploys also increase the nonce of the deployer, and make it the owner of the new contract.
+    fn create_account_if_necessary(&mut self, tx: &mut TxData) -> Option<Address> {
+        let tx_contract = tx.new_contract.as_ref()?.clone_contract();
+        let deployer_nonce = match self.accounts.get_mut(&tx.from) {
+            Some(deployer) => {
+                deployer.nonce += 1;
+                deployer.nonce - 1
+            },
+            None => 0,
+        };
+        if tx.derive_new_address {
+            tx.to = new_contract_address(&tx.from, deployer_nonce);
+        }
+        if self.accounts.contains_key(&tx.to) {
+            panic!("Account already exists");
+        }
+        let mut account = AccountData::new(tx.to.clone());
+        account.contract = Some(tx_contract);
+        account.contract_owner = Some(tx.from.clone());
+        self.accounts.insert(tx.to.clone(), account);
+        Some(tx.to.clone())
+    }
+
+    /// Moves the value of the transaction from the sender to the recipient, before the contract is called.
+    /// Returns false if the sender cannot cover it.
+    fn transfer_call_value(&mut self, tx: &TxData) -> bool {
+        if tx.call_value == 0.into() {
+            return true;
+        }
+        match self.accounts.get_mut(&tx.from) {
+            Some(sender) if sender.balance >= tx.call_value => {
+                sender.balance -= &tx.call_value;
+            },
+            _ => return false,
         }
+        match self.accounts.get_mut(&tx.to) {
+            None => panic!("Account not found"),
+            Some(recipient) => {
+                recipient.balance += &tx.call_value;
+            },
+        }
+        true
     }
 
     pub fn set_result_status(&mut self, status: i32) {
         self.current_result.result_status = status;
     }
+
+    fn set_result_error(&mut self, message: &[u8]) {
+        self.current_result.result_status = USER_ERROR_STATUS;
+        self.current_result.result_message = message.to_vec();
+    }
     
     pub fn add_result(&mut self, result: Vec<u8>) {
         self.current_result.result_values.push(result);
@@ -187,11 +330,83 @@ impl AndesMockState {
     
     fn clear_result(&mut self) {
         self.current_result = TxResult::empty();
+        self.num_async_calls_in_tx = 0;
     }
     
     fn get_result(&self) -> TxResult {
         self.current_result.clone()
     }
+
+    fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            accounts: self.accounts.iter()
+                .map(|(address, acct)| (address.clone(), AccountSnapshot {
+                    nonce: acct.nonce,
+                    balance: acct.balance.clone(),
+                    storage: acct.storage.clone(),
+                    dct_balances: acct.dct_balances.clone(),
+                    username: acct.username.clone(),
+                }))
+                .collect(),
+            num_async_calls: self.async_calls.len(),
+        }
+    }
+
+    fn revert_to(&mut self, snapshot: StateSnapshot) {
+        let mut saved_accounts = snapshot.accounts;
+        // accounts deployed by the reverted transaction are dropped
+        self.accounts.retain(|address, _| saved_accounts.contains_key(address));
+        for (address, acct) in self.accounts.iter_mut() {
+            let saved = saved_accounts.remove(address).unwrap();
+            acct.nonce = saved.nonce;
+            acct.balance = saved.balance;
+            acct.storage = saved.storage;
+            acct.dct_balances = saved.dct_balances;
+            acct.username = saved.username;
+        }
+        self.async_calls.truncate(snapshot.num_async_calls);
+    }
+}
+
+/// Account state saved before a transaction, to be restored if it fails.
+struct AccountSnapshot {
+    nonce: u64,
+    balance: BigInt,
+    storage: HashMap<Vec<u8>, Vec<u8>>,
+    dct_balances: HashMap<Vec<u8>, BigInt>,
+    username: Vec<u8>,
+}
+
+struct StateSnapshot {
+    accounts: HashMap<Address, AccountSnapshot>,
+    num_async_calls: usize,
+}
+
+/// Address of the contract deployed by `deployer` when it has the given nonce.
+/// The address is only unique within the mock, it is not the one the node would compute.
+pub fn new_contract_address(deployer: &Address, nonce: u64) -> Address {
+    let mut hasher = Keccak256::new();
+    hasher.input(deployer.as_bytes());
+    hasher.input(nonce.to_le_bytes());
+    let hash: [u8; 32] = hasher.result().into();
+    let mut address = [0u8; ADDRESS_LENGTH];
+    address[8..30].copy_from_slice(&hash[8..30]);
+    address[30..].copy_from_slice(&deployer.as_bytes()[30..]);
+    Address::from(address)
+}
+
+fn callback_tx(async_call: &PendingAsyncCall, results: Vec<Vec<u8>>) -> TxData {
+    TxData {
+        func_name: "callBack",
+        new_contract: None,
+        args: results,
+        call_value: 0.into(),
+        from: async_call.to.clone(),
+        to: async_call.from.clone(),
+        tx_hash: async_call.tx_hash.clone(),
+        callback_call_index: async_call.call_index,
+        derive_new_address: false,
+    }
 }
 
 impl AndesMockRef {
@@ -209,41 +424,97 @@ impl AndesMockRef {
         }
     }
 
-    pub fn execute_tx(&self, tx: TxData) -> TxResult {
+    pub fn execute_tx(&self, mut tx: TxData) -> TxResult {
         {
             let mut state = self.state_ref.borrow_mut();
-            state.create_account_if_necessary(&tx);    
+            let new_address = state.create_account_if_necessary(&mut tx);
+            let funded = state.transfer_call_value(&tx);
             state.current_tx = Some(tx);
             state.clear_result();
+            state.current_result.new_address = new_address;
+            if !funded {
+                state.set_result_error(INSUFFICIENT_FUNDS);
+                panic!("insufficient funds for the call value");
+            }
         }
         
-        let state = self.state_ref.borrow();
-        let tx_ref = state.current_tx.as_ref().unwrap();
+        let func_name = self.state_ref.borrow().current_tx.as_ref().unwrap().func_name;
         
         let contract = self.get_contract();
 
         // contract call
         // important: state cannot be borrowed at this point
-        contract.call(tx_ref.func_name);
+        contract.call(func_name);
         
         let state = self.state_ref.borrow();
         state.get_result()
     }
 
+    /// Like `execute_tx`, but if the contract signals an error the transaction is reverted
+    /// and the error is returned in the result, instead of panicking.
+    pub fn try_execute_tx(&self, tx: TxData) -> TxResult {
+        let snapshot = self.state_ref.borrow().snapshot();
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.execute_tx(tx)));
+        match outcome {
+            Ok(tx_result) => tx_result,
+            Err(panic_payload) => {
+                let mut state = self.state_ref.borrow_mut();
+                if state.current_result.result_status != USER_ERROR_STATUS {
+                    // not a contract error, but a bug in the contract or the test
+                    drop(state);
+                    std::panic::resume_unwind(panic_payload);
+                }
+                state.revert_to(snapshot);
+                state.current_result.new_address = None;
+                state.get_result()
+            },
+        }
+    }
+
+    /// Runs a view outside of any transaction, the way a node answers queries.
+    /// Errors are returned in the result, and any change the view made is reverted.
+    pub fn execute_query(&self, contract_address: &Address, func_name: &'static str, args: Vec<Vec<u8>>) -> TxResult {
+        {
+            let state = self.state_ref.borrow();
+            match state.accounts.get(contract_address) {
+                Some(AccountData{ contract: Some(contract), .. }) => {
+                    if !contract.is_view(func_name) {
+                        panic!("`{}` is not a view, it can only be called in a transaction", func_name);
+                    }
+                },
+                _ => panic!("Recipient account is not a smart contract"),
+            }
+        }
+        self.try_execute_tx(TxData {
+            func_name,
+            new_contract: None,
+            args,
+            call_value: 0.into(),
+            from: Address::zero(),
+            to: contract_address.clone(),
+            tx_hash: H256::zero(),
+            callback_call_index: 0,
+            derive_new_address: false,
+        })
+    }
+
     /// To be used for writing small tests.
     pub fn set_dummy_tx(&self, addr: &Address) {
-        let tx = TxData {
+        let mut tx = TxData {
             func_name: "",
             new_contract: None,
             args: Vec::new(),
             call_value: 0.into(),
             from: addr.clone(),
             to: addr.clone(),
+            tx_hash: H256::zero(),
+            callback_call_index: 0,
+            derive_new_address: false,
         };
 
         {
             let mut state = self.state_ref.borrow_mut();
-            state.create_account_if_necessary(&tx);    
+            state.create_account_if_necessary(&mut tx);
             state.current_tx = Some(tx);
             state.clear_result();
         }
@@ -254,6 +525,75 @@ impl AndesMockRef {
         state.accounts.insert(acct.address.clone(), acct);
     }
 
+    /// Removes and returns the async calls made so far.
+    pub fn take_async_calls(&self) -> Vec<PendingAsyncCall> {
+        let mut state = self.state_ref.borrow_mut();
+        core::mem::take(&mut state.async_calls)
+    }
+
+    /// Runs the callback of the contract that made the async call, as if the call returned the given results.
+    pub fn execute_callback(&self, async_call: &PendingAsyncCall, results: Vec<Vec<u8>>) -> TxResult {
+        self.execute_tx(callback_tx(async_call, results))
+    }
+
+    /// Like `execute_callback`, but errors are returned in the result, as with `try_execute_tx`.
+    pub fn try_execute_callback(&self, async_call: &PendingAsyncCall, results: Vec<Vec<u8>>) -> TxResult {
+        self.try_execute_tx(callback_tx(async_call, results))
+    }
+
+    fn perform_async_call(&self, to: &Address, amount: &RustBigUint, data: &[u8], gas_limit: Option<u64>) {
+        let builtin_call = match BuiltinFunctionCall::parse(data) {
+            Ok(builtin_call) => builtin_call,
+            Err(e) => panic!("invalid built-in function call: {}", String::from_utf8_lossy(e.as_bytes())),
+        };
+        let mut state = self.state_ref.borrow_mut();
+        if let Some(builtin_call) = builtin_call {
+            if *amount.value() > 0.into() {
+                panic!("built-in function calls cannot transfer value");
+            }
+            // executed right away, but still recorded below, since they can have callbacks too
+            state.execute_builtin_call(to, builtin_call);
+        }
+
+        let call_index = state.num_async_calls_in_tx;
+        state.num_async_calls_in_tx += 1;
+        let tx = state.current_tx.as_ref().unwrap();
+        let async_call = PendingAsyncCall {
+            from: tx.to.clone(),
+            to: to.clone(),
+            amount: amount.value().clone(),
+            gas_limit,
+            data: data.to_vec(),
+            tx_hash: tx.tx_hash.clone(),
+            call_index,
+        };
+        state.async_calls.push(async_call);
+    }
+
+    pub fn get_dct_balance(&self, address: &Address, token_name: &[u8]) -> BigInt {
+        let state = self.state_ref.borrow();
+        match state.accounts.get(address) {
+            None => panic!("Account not found!"),
+            Some(acct) => acct.dct_balances.get(token_name).cloned().unwrap_or_else(|| 0.into()),
+        }
+    }
+
+    pub fn set_balance(&self, address: &Address, balance: BigInt) {
+        let mut state = self.state_ref.borrow_mut();
+        match state.accounts.get_mut(address) {
+            None => panic!("Account not found!"),
+            Some(acct) => acct.balance = balance,
+        }
+    }
+
+    pub fn get_username(&self, address: &Address) -> Vec<u8> {
+        let state = self.state_ref.borrow();
+        match state.accounts.get(address) {
+            None => panic!("Account not found!"),
+            Some(acct) => acct.username.clone(),
+        }
+    }
+
     pub fn print_accounts(&self) {
         let state = self.state_ref.borrow();
         let mut accounts_buf = String::new();
@@ -264,6 +604,54 @@ impl AndesMockRef {
     }
 }
 
+impl numbat_wasm::EndpointCaller for AndesMockRef {
+    type BigInt = RustBigInt;
+    type BigUint = RustBigUint;
+
+    fn call_endpoint(&self,
+        caller: &Address,
+        contract_address: &Address,
+        call_value: RustBigUint,
+        endpoint_name: &'static str,
+        args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Vec<u8>> {
+
+        let tx = TxData {
+            func_name: endpoint_name,
+            new_contract: None,
+            args,
+            call_value: call_value.0,
+            from: caller.clone(),
+            to: contract_address.clone(),
+            tx_hash: H256::zero(),
+            callback_call_index: 0,
+            derive_new_address: false,
+        };
+        let tx_result = self.try_execute_tx(tx);
+        if tx_result.result_status == 0 {
+            Ok(tx_result.result_values)
+        } else {
+            Err(tx_result.result_message)
+        }
+    }
+
+    fn deploy_contract(&self,
+        deployer: &Address,
+        contract: Box<dyn CallableContract>,
+        call_value: RustBigUint,
+        args: Vec<Vec<u8>>) -> Result<Address, Vec<u8>> {
+
+        let mut tx = TxData::new_deploy(contract, deployer.clone());
+        tx.call_value = call_value.0;
+        tx.args = args;
+        let tx_result = self.try_execute_tx(tx);
+        if tx_result.result_status == 0 {
+            Ok(tx_result.new_address.unwrap())
+        } else {
+            Err(tx_result.result_message)
+        }
+    }
+}
+
 impl numbat_wasm::ContractHookApi<RustBigInt, RustBigUint> for AndesMockRef {
     fn get_sc_address(&self) -> Address {
         let state = self.state_ref.borrow();
@@ -274,7 +662,15 @@ impl numbat_wasm::ContractHookApi<RustBigInt, RustBigUint> for AndesMockRef {
     }
 
     fn get_owner_address(&self) -> Address {
-        panic!("get_sc_address not yet implemented")
+        let sc_address = self.get_sc_address();
+        let state = self.state_ref.borrow();
+        match state.accounts.get(&sc_address) {
+            None => panic!("Account not found!"),
+            Some(acct) => match &acct.contract_owner {
+                None => panic!("Contract owner not set"),
+                Some(owner) => owner.clone(),
+            },
+        }
     }
 
     fn get_caller(&self) -> Address {
@@ -285,8 +681,12 @@ impl numbat_wasm::ContractHookApi<RustBigInt, RustBigUint> for AndesMockRef {
         }
     }
 
-    fn get_balance(&self, _address: &Address) -> RustBigUint {
-        panic!("get_balance not yet implemented")
+    fn get_balance(&self, address: &Address) -> RustBigUint {
+        let state = self.state_ref.borrow();
+        match state.accounts.get(address) {
+            None => panic!("Account not found!"),
+            Some(acct) => acct.balance.clone().into(),
+        }
     }
 
     fn storage_store(&self, key: &[u8], value: &[u8]) {
@@ -401,12 +801,32 @@ impl numbat_wasm::ContractHookApi<RustBigInt, RustBigUint> for AndesMockRef {
         }
     }
 
-    fn async_call(&self, _to: &Address, _amount: &RustBigUint, _data: &[u8]) {
-        panic!("async_call not yet implemented");
+    fn async_call(&self, to: &Address, amount: &RustBigUint, data: &[u8]) {
+        self.perform_async_call(to, amount, data, None);
+    }
+
+    fn async_call_with_gas(&self, to: &Address, amount: &RustBigUint, data: &[u8], gas_limit: u64) {
+        self.perform_async_call(to, amount, data, Some(gas_limit));
+    }
+
+    fn get_num_async_calls(&self) -> u32 {
+        self.state_ref.borrow().num_async_calls_in_tx
+    }
+
+    fn get_callback_call_index(&self) -> u32 {
+        let state = self.state_ref.borrow();
+        match &state.current_tx {
+            None => panic!("Tx not initialized!"),
+            Some(tx) => tx.callback_call_index,
+        }
     }
 
     fn get_tx_hash(&self) -> H256 {
-        panic!("get_tx_hash not yet implemented");
+        let state = self.state_ref.borrow();
+        match &state.current_tx {
+            None => panic!("Tx not initialized!"),
+            Some(tx) => tx.tx_hash.clone(),
+        }
     }
 
     fn get_gas_left(&self) -> i64 {
@@ -443,6 +863,40 @@ impl numbat_wasm::ContractHookApi<RustBigInt, RustBigUint> for AndesMockRef {
 }
 
 impl AndesMockState {
+    /// Applies the effects of a built-in function call made by the current contract.
+    /// Developer rewards are not tracked, so claiming them has no effect.
+    fn execute_builtin_call(&mut self, to: &Address, builtin_call: BuiltinFunctionCall) {
+        let from = self.current_tx.as_ref().unwrap().to.clone();
+        match builtin_call {
+            // the follow-up call, if any, stays in the recorded async call, like other contract calls
+            BuiltinFunctionCall::DctTransfer { token_name, amount, .. } => {
+                let amount = BigInt::from_signed_bytes_be(&[&[0u8][..], amount.as_slice()].concat());
+                let sender = self.accounts.get_mut(&from).unwrap();
+                let sender_balance = sender.dct_balances.entry(token_name.clone()).or_insert_with(|| 0.into());
+                if *sender_balance < amount {
+                    panic!("Insufficient DCT balance!");
+                }
+                *sender_balance -= &amount;
+                match self.accounts.get_mut(to) {
+                    None => panic!("Account not found!"),
+                    Some(acct) => {
+                        *acct.dct_balances.entry(token_name).or_insert_with(|| 0.into()) += amount;
+                    }
+                }
+            },
+            BuiltinFunctionCall::SaveKeyValue(pairs) => {
+                let sender = self.accounts.get_mut(&from).unwrap();
+                for (key, value) in pairs.into_iter() {
+                    sender.storage.insert(key, value);
+                }
+            },
+            BuiltinFunctionCall::SetUsername(username) => {
+                self.accounts.get_mut(&from).unwrap().username = username;
+            },
+            BuiltinFunctionCall::ClaimDeveloperRewards => {},
+        }
+    }
+
     fn get_argument_vec(&self, arg_index: i32) -> Vec<u8> {
         let arg_idx_usize: usize = arg_index as usize;
         match &self.current_tx {
@@ -480,8 +934,12 @@ impl numbat_wasm::ContractIOApi<RustBigInt, RustBigUint> for AndesMockRef {
         arg.len()
     }
 
-    fn copy_argument_to_slice(&self, _arg_index: i32, _slice: &mut [u8]) {
-        panic!("copy_argument_to_slice not yet implemented")
+    fn copy_argument_to_slice(&self, arg_index: i32, slice: &mut [u8]) {
+        let arg = self.get_argument_vec(arg_index);
+        if arg.len() != slice.len() {
+            self.signal_error(err_msg::ARG_BAD_LENGTH);
+        }
+        slice.copy_from_slice(arg.as_slice());
     }
 
     fn get_argument_vec(&self, arg_index: i32) -> Vec<u8> {
@@ -504,9 +962,10 @@ impl numbat_wasm::ContractIOApi<RustBigInt, RustBigUint> for AndesMockRef {
         BigInt::from_signed_bytes_be(&bytes).into()
     }
 
-    #[inline]
-    fn get_argument_big_uint(&self, _arg_index: i32) -> RustBigUint {
-        panic!("get_argument_big_uint not yet implemented")
+    fn get_argument_big_uint(&self, arg_index: i32) -> RustBigUint {
+        let state = self.state_ref.borrow();
+        let bytes = state.get_argument_vec(arg_index);
+        RustBigUint::from_bytes_be(&bytes)
     }
 
     #[inline]
@@ -550,6 +1009,7 @@ impl numbat_wasm::ContractIOApi<RustBigInt, RustBigUint> for AndesMockRef {
     }
 
     fn signal_error(&self, message: &[u8]) -> ! {
+        self.state_ref.borrow_mut().set_result_error(message);
         let s = std::str::from_utf8(message);
         panic!("signal_error was called with message: {}", s.unwrap())
     }
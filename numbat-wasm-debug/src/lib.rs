@@ -47,4 +47,297 @@ mod esd_light_tests {
     fn test_big_uint_serialization() {
         ser_deser_ok(RustBigUint::from(5u32), &[5u8]);
     }
+
+    #[test]
+    fn test_big_num_type_descriptor() {
+        assert_eq!(RustBigUint::type_descriptor(), TypeDescriptor::BigUint);
+        assert_eq!(Option::<RustBigInt>::type_descriptor(), TypeDescriptor::Option(Box::new(TypeDescriptor::BigInt)));
+    }
+
+    #[derive(PartialEq, Debug)]
+    struct StoredV1(u32);
+
+    impl Encode for StoredV1 {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+            self.0.dep_encode_to(dest)
+        }
+    }
+
+    /// Later layout of `StoredV1`, with an extra trailing field.
+    #[derive(PartialEq, Debug)]
+    struct StoredV2(u32, Option<u8>);
+
+    impl Encode for StoredV2 {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+            self.0.dep_encode_to(dest)?;
+            self.1.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for StoredV2 {
+        fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+            Ok(StoredV2(
+                u32::dep_decode(input).map_err(|e| e.at_index(0))?,
+                dep_decode_or_default(input).map_err(|e| e.at_index(1))?,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_storage_trailing_field_evolution() {
+        let address = numbat_wasm::Address::from([1u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&address);
+
+        numbat_wasm::storage_set(&mock_ref, &b"old"[..], &StoredV1(5));
+        let upgraded: StoredV2 = numbat_wasm::storage_get(&mock_ref, &b"old"[..]);
+        assert_eq!(upgraded, StoredV2(5, None));
+
+        numbat_wasm::storage_set(&mock_ref, &b"new"[..], &StoredV2(5, Some(3)));
+        let reloaded: StoredV2 = numbat_wasm::storage_get(&mock_ref, &b"new"[..]);
+        assert_eq!(reloaded, StoredV2(5, Some(3)));
+    }
+
+    #[test]
+    fn test_builtin_function_calls() {
+        use numbat_wasm::ContractHookApi;
+        use numbat_wasm::call_data::BuiltinFunctionCall;
+
+        let mock_ref = AndesMockState::new_ref();
+        let sender = numbat_wasm::Address::from([1u8; 32]);
+        let receiver = numbat_wasm::Address::from([2u8; 32]);
+        for address in [&sender, &receiver].iter() {
+            let mut account = AccountData::new((*address).clone());
+            account.dct_balances.insert(b"TKN".to_vec(), 100.into());
+            mock_ref.add_account(account);
+        }
+        mock_ref.set_dummy_tx(&sender);
+        let no_value = RustBigUint::from(0u32);
+
+        let transfer = BuiltinFunctionCall::dct_transfer(&b"TKN"[..], &30u32).unwrap();
+        mock_ref.async_call(&receiver, &no_value, transfer.serialize().as_slice());
+        assert_eq!(mock_ref.get_dct_balance(&sender, &b"TKN"[..]), 70.into());
+        assert_eq!(mock_ref.get_dct_balance(&receiver, &b"TKN"[..]), 130.into());
+        assert_eq!(mock_ref.get_dct_balance(&receiver, &b"OTHER"[..]), 0.into());
+
+        // only the transfer is executed, the call is recorded for the test to run
+        let mut then_call = numbat_wasm::CallData::new(&b"deposit"[..]);
+        then_call.push_arg(&5u32).unwrap();
+        let transfer_and_call = BuiltinFunctionCall::dct_transfer_and_call(&b"TKN"[..], &20u32, then_call.clone()).unwrap();
+        mock_ref.async_call(&receiver, &no_value, transfer_and_call.serialize().as_slice());
+        assert_eq!(mock_ref.get_dct_balance(&sender, &b"TKN"[..]), 50.into());
+        assert_eq!(mock_ref.get_dct_balance(&receiver, &b"TKN"[..]), 150.into());
+        let async_calls = mock_ref.take_async_calls();
+        match BuiltinFunctionCall::parse(async_calls[1].data.as_slice()) {
+            Ok(Some(BuiltinFunctionCall::DctTransfer { then_call: Some(recorded), .. })) => assert_eq!(recorded, then_call),
+            _ => panic!("DCT transfer with call expected"),
+        }
+
+        let save = BuiltinFunctionCall::save_key_value(&[(&b"key"[..], &b"value"[..])]);
+        mock_ref.async_call(&sender, &no_value, save.serialize().as_slice());
+        assert_eq!(mock_ref.storage_load(&b"key"[..]), b"value".to_vec());
+    }
+
+    #[test]
+    fn test_async_calls_with_callback_closures() {
+        use numbat_wasm::ContractHookApi;
+        use numbat_wasm::{AsyncCall, CallbackClosure};
+        use numbat_wasm::async_call::*;
+
+        let caller = numbat_wasm::Address::from([1u8; 32]);
+        let other = numbat_wasm::Address::from([2u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&caller);
+
+        let mut closure1 = CallbackClosure::new(&b"cb"[..]);
+        closure1.push_arg(&1u8).unwrap();
+        let mut call1 = AsyncCall::new(&other, &b"deposit"[..])
+            .with_value(RustBigUint::from(10u32))
+            .with_callback(closure1);
+        call1.push_arg(&5u32).unwrap();
+        call1.execute(&mock_ref);
+
+        let mut closure2 = CallbackClosure::new(&b"cb"[..]);
+        closure2.push_arg(&2u8).unwrap();
+        AsyncCall::<RustBigUint>::new(&other, &b"withdraw"[..])
+            .with_gas_limit(5000)
+            .with_callback(closure2)
+            .execute(&mock_ref);
+        assert_eq!(mock_ref.get_num_async_calls(), 2);
+
+        let async_calls = mock_ref.take_async_calls();
+        assert_eq!(async_calls.len(), 2);
+        assert_eq!(async_calls[0].data, b"deposit@05".to_vec());
+        assert_eq!(async_calls[0].amount, 10.into());
+        assert_eq!(async_calls[0].gas_limit, None);
+        assert_eq!(async_calls[1].data, b"withdraw".to_vec());
+        assert_eq!(async_calls[1].gas_limit, Some(5000));
+        assert_eq!(async_calls[1].call_index, 1);
+
+        // each call keeps its own closure
+        let tx_hash = mock_ref.get_tx_hash();
+        assert_eq!(mock_ref.storage_load(callback_closure_key(&tx_hash, 0).as_slice()), b"cb@01".to_vec());
+        assert_eq!(mock_ref.storage_load(callback_closure_key(&tx_hash, 1).as_slice()), b"cb@02".to_vec());
+        assert_eq!(load_callback_closure(&mock_ref), b"cb@01".to_vec());
+        clear_callback_closure(&mock_ref);
+        assert_eq!(load_callback_closure(&mock_ref), Vec::<u8>::new());
+        assert_eq!(mock_ref.storage_load(callback_closure_key(&tx_hash, 1).as_slice()), b"cb@02".to_vec());
+    }
+
+    #[test]
+    fn test_builtin_function_call_callback_closure() {
+        use numbat_wasm::ContractHookApi;
+        use numbat_wasm::{AsyncCall, CallbackClosure};
+        use numbat_wasm::async_call::*;
+        use numbat_wasm::call_data::SAVE_KEY_VALUE_FUNC_NAME;
+
+        let caller = numbat_wasm::Address::from([1u8; 32]);
+        let other = numbat_wasm::Address::from([2u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&caller);
+        mock_ref.add_account(AccountData::new(other.clone()));
+
+        let mut save = AsyncCall::<RustBigUint>::new(&other, SAVE_KEY_VALUE_FUNC_NAME)
+            .with_callback(CallbackClosure::new(&b"saved"[..]));
+        save.push_argument_bytes(&b"key"[..]);
+        save.push_argument_bytes(&b"value"[..]);
+        save.execute(&mock_ref);
+        AsyncCall::<RustBigUint>::new(&other, &b"deposit"[..])
+            .with_callback(CallbackClosure::new(&b"deposited"[..]))
+            .execute(&mock_ref);
+
+        // the built-in function call took an index, so the second closure did not replace its closure
+        let tx_hash = mock_ref.get_tx_hash();
+        assert_eq!(mock_ref.storage_load(callback_closure_key(&tx_hash, 0).as_slice()), b"saved".to_vec());
+        assert_eq!(mock_ref.storage_load(callback_closure_key(&tx_hash, 1).as_slice()), b"deposited".to_vec());
+
+        let async_calls = mock_ref.take_async_calls();
+        assert_eq!(async_calls.len(), 2);
+        assert_eq!(async_calls[0].call_index, 0);
+        assert_eq!(async_calls[1].call_index, 1);
+        assert_eq!(async_calls[1].data, b"deposit".to_vec());
+        assert_eq!(mock_ref.storage_load(&b"key"[..]), b"value".to_vec());
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        use numbat_wasm::{FixedPoint, Rounding};
+
+        type Price = FixedPoint<RustBigUint, 4>;
+        let price = |raw: u64| Price::from_raw(RustBigUint::from(raw));
+
+        // 1.5 + 0.25
+        assert_eq!(price(15000) + price(2500), price(17500));
+        assert_eq!(price(15000) - price(2500), price(12500));
+        assert_eq!(price(2500).checked_sub(&price(15000)), None);
+        assert_eq!(Price::from_integer(RustBigUint::from(3u32)), price(30000));
+
+        // 1.5 * 0.0003 = 0.00045
+        assert_eq!(price(15000).mul(&price(3), Rounding::Down), price(4));
+        assert_eq!(price(15000).mul(&price(3), Rounding::Nearest), price(5));
+        // 1 / 3
+        assert_eq!(price(10000).div(&price(30000), Rounding::Down), price(3333));
+        assert_eq!(price(10000).div(&price(30000), Rounding::Up), price(3334));
+        assert_eq!(price(17500).to_integer(Rounding::Nearest), RustBigUint::from(2u32));
+
+        let finer: FixedPoint<RustBigUint, 6> = price(15001).rescale(Rounding::Down);
+        assert_eq!(finer.raw(), &RustBigUint::from(1500100u32));
+        let coarser: FixedPoint<RustBigUint, 2> = price(15051).rescale(Rounding::Nearest);
+        assert_eq!(coarser.raw(), &RustBigUint::from(151u32));
+
+        // encoded as the underlying integer
+        ser_deser_ok(price(0x1234), &[0x12, 0x34]);
+        assert_eq!(<(Price, u8)>::top_decode(&mut &[0, 0, 0, 1, 5, 7][..]), Ok((price(5), 7)));
+
+        let address = numbat_wasm::Address::from([1u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&address);
+        numbat_wasm::storage_set(&mock_ref, &b"price"[..], &price(15000));
+        let loaded: Price = numbat_wasm::storage_get(&mock_ref, &b"price"[..]);
+        assert_eq!(loaded, price(15000));
+    }
+
+    fn read_only_mock() -> numbat_wasm::ReadOnlyApi<AndesMockRef> {
+        let address = numbat_wasm::Address::from([1u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&address);
+        numbat_wasm::storage_set(&mock_ref, &b"key"[..], &5u32);
+        numbat_wasm::ReadOnlyApi::new(mock_ref)
+    }
+
+    #[test]
+    fn test_read_only_api_reads() {
+        let api = read_only_mock();
+        let loaded: u32 = numbat_wasm::storage_get(&api, &b"key"[..]);
+        assert_eq!(loaded, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "views cannot change state")]
+    fn test_read_only_api_rejects_writes() {
+        let api = read_only_mock();
+        numbat_wasm::storage_set(&api, &b"key"[..], &6u32);
+    }
+
+    #[test]
+    fn test_storage_opt_is_empty_clear() {
+        let address = numbat_wasm::Address::from([1u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&address);
+
+        assert!(numbat_wasm::storage_is_empty(&mock_ref, &b"key"[..]));
+        assert_eq!(numbat_wasm::storage_get_opt::<_, _, _, StoredV2>(&mock_ref, &b"key"[..]), None);
+
+        numbat_wasm::storage_set_opt(&mock_ref, &b"key"[..], &StoredV2(5, None));
+        assert!(!numbat_wasm::storage_is_empty(&mock_ref, &b"key"[..]));
+        assert_eq!(numbat_wasm::storage_get_opt(&mock_ref, &b"key"[..]), Some(StoredV2(5, None)));
+
+        numbat_wasm::storage_clear(&mock_ref, &b"key"[..]);
+        assert!(numbat_wasm::storage_is_empty(&mock_ref, &b"key"[..]));
+        assert_eq!(numbat_wasm::storage_get_opt::<_, _, _, StoredV2>(&mock_ref, &b"key"[..]), None);
+
+        numbat_wasm::storage_set_opt(&mock_ref, &b"zero"[..], &0u64);
+        assert!(!numbat_wasm::storage_is_empty(&mock_ref, &b"zero"[..]));
+        assert_eq!(numbat_wasm::storage_get_opt(&mock_ref, &b"zero"[..]), Some(0u64));
+    }
+
+    /// Stores its argument, also as its username, then fails if asked to.
+    struct StoreThenFail(AndesMockRef);
+
+    impl numbat_wasm::CallableContract for StoreThenFail {
+        fn call(&self, fn_name: &'static str) {
+            use numbat_wasm::{ContractHookApi, ContractIOApi};
+            if fn_name == "init" {
+                return;
+            }
+            let value = self.0.get_argument_vec(0);
+            self.0.storage_store(&b"key"[..], value.as_slice());
+            let set_username = numbat_wasm::call_data::BuiltinFunctionCall::set_username(value.as_slice());
+            self.0.async_call(&self.0.get_sc_address(), &0u32.into(), set_username.serialize().as_slice());
+            if fn_name == "fail" {
+                self.0.signal_error(&b"failed on purpose"[..]);
+            }
+            self.0.finish_slice_u8(value.as_slice());
+        }
+
+        fn is_view(&self, _fn_name: &str) -> bool {
+            false
+        }
+
+        fn clone_contract(&self) -> Box<dyn numbat_wasm::CallableContract> {
+            Box::new(StoreThenFail(self.0.clone()))
+        }
+    }
+
+    #[test]
+    fn test_endpoint_caller_reverts_errors() {
+        use numbat_wasm::{ContractHookApi, EndpointCaller};
+        let mock_ref = AndesMockState::new_ref();
+        let owner = numbat_wasm::Address::from([1u8; 32]);
+        let sc = numbat_wasm::Address::from([2u8; 32]);
+        mock_ref.execute_tx(TxData::new_create(Box::new(StoreThenFail(mock_ref.clone())), owner.clone(), sc.clone()));
+
+        let result = mock_ref.call_endpoint(&owner, &sc, 0u32.into(), "store", vec![vec![5]]);
+        assert_eq!(result, Ok(vec![vec![5]]));
+
+        let result = mock_ref.call_endpoint(&owner, &sc, 0u32.into(), "fail", vec![vec![6]]);
+        assert_eq!(result, Err(b"failed on purpose".to_vec()));
+        mock_ref.set_dummy_tx(&sc);
+        assert_eq!(mock_ref.storage_load(&b"key"[..]), vec![5]);
+        assert_eq!(mock_ref.get_username(&sc), vec![5]);
+    }
 }
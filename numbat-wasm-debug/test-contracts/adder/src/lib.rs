@@ -0,0 +1,32 @@
+#![no_std]
+#![allow(unused_attributes)]
+
+imports!();
+
+#[numbat_wasm_derive::contract(AdderImpl, proxy(AdderProxy))]
+pub trait Adder {
+    #[init]
+    fn init(&self, initial_value: u64) {
+        self.set_sum(&BigUint::from(initial_value));
+    }
+
+    #[view(getSum)]
+    #[storage_get("sum")]
+    fn get_sum(&self) -> BigUint;
+
+    #[storage_set("sum")]
+    fn set_sum(&self, sum: &BigUint);
+
+    #[endpoint]
+    fn add(&self, value: u64) {
+        let sum = self.get_sum() + BigUint::from(value);
+        self.set_sum(&sum);
+    }
+
+    #[payable]
+    #[endpoint(addPayment)]
+    fn add_payment(&self, #[payment] payment: BigUint, times: u32) {
+        let sum = self.get_sum() + payment * BigUint::from(times);
+        self.set_sum(&sum);
+    }
+}
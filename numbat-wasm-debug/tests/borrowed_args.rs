@@ -0,0 +1,58 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(BorrowedArgsImpl)]
+pub trait BorrowedArgs {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(describe)]
+    fn describe(&self, data: &[u8], name: &str, n: u32) -> MultiResult3<usize, Vec<u8>, u32> {
+        (data.len(), name.as_bytes().to_vec(), n).into()
+    }
+
+    #[endpoint(countAll)]
+    fn count_all(&self, name: &str, #[var_args] rest: VarArgs<u32>) -> MultiResult2<Vec<u8>, usize> {
+        (name.as_bytes().to_vec(), rest.len()).into()
+    }
+}
+
+fn deploy() -> BorrowedArgsClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    BorrowedArgsClient::deploy(AndesMockState::new_ref_with_account(&owner), &owner).unwrap()
+}
+
+#[test]
+fn test_borrowed_fixed_args() {
+    let sc = deploy();
+    let mut tx = TxData::new_call("describe", sc.caller.clone(), sc.address.clone());
+    tx.add_arg(vec![1, 2, 3]);
+    tx.add_arg(b"abc".to_vec());
+    tx.add_arg(vec![5]);
+    let result = sc.caller_api.execute_tx(tx);
+    assert_eq!(result.result_values, vec![vec![3], b"abc".to_vec(), vec![5]]);
+}
+
+#[test]
+fn test_borrowed_var_args() {
+    let sc = deploy();
+    let mut tx = TxData::new_call("countAll", sc.caller.clone(), sc.address.clone());
+    tx.add_arg(b"xy".to_vec());
+    tx.add_arg(vec![1]);
+    tx.add_arg(vec![2]);
+    let result = sc.caller_api.execute_tx(tx);
+    assert_eq!(result.result_values, vec![b"xy".to_vec(), vec![2]]);
+}
+
+#[test]
+fn test_borrowed_arg_invalid_utf8() {
+    let sc = deploy();
+    let mut tx = TxData::new_call("countAll", sc.caller.clone(), sc.address.clone());
+    tx.add_arg(vec![0xff, 0xfe]);
+    let result = sc.caller_api.try_execute_tx(tx);
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"argument decode error (name): invalid value".to_vec());
+}
@@ -0,0 +1,128 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(VaultImpl)]
+pub trait Vault {
+    #[init]
+    #[payable]
+    fn init(&self, #[payment] payment: BigUint) {
+        self.set_deposits(&payment);
+    }
+
+    #[view(getOwner)]
+    fn get_owner(&self) -> Address {
+        self.get_owner_address()
+    }
+
+    #[view(getDeposits)]
+    #[storage_get("deposits")]
+    fn get_deposits(&self) -> BigUint;
+
+    #[storage_set("deposits")]
+    fn set_deposits(&self, deposits: &BigUint);
+
+    #[payable]
+    #[endpoint]
+    fn deposit(&self, #[payment] payment: BigUint) {
+        self.set_deposits(&(self.get_deposits() + payment));
+    }
+
+    #[view]
+    fn sum(&self, first: u64, #[var_args] rest: VarArgs<u64>) -> u64 {
+        rest.iter().fold(first, |sum, x| sum + x)
+    }
+}
+
+fn new_mock() -> (AndesMockRef, Address) {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    mock_ref.set_balance(&owner, 100.into());
+    (mock_ref, owner)
+}
+
+fn deploy_tx(mock_ref: &AndesMockRef, owner: &Address, call_value: u32) -> TxData {
+    let mut tx = TxData::new_deploy(Box::new(VaultImpl::new(mock_ref.clone())), owner.clone());
+    tx.set_call_value(call_value.into());
+    tx
+}
+
+#[test]
+fn test_deploy_address_follows_nonce() {
+    let (mock_ref, owner) = new_mock();
+    let first = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 0)).new_address.unwrap();
+    let second = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 0)).new_address.unwrap();
+    // the nonce of the deployer is increased by every deploy
+    assert_eq!(first, new_contract_address(&owner, 0));
+    assert_eq!(second, new_contract_address(&owner, 1));
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_deploy_sets_owner() {
+    let (mock_ref, owner) = new_mock();
+    let sc = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 0)).new_address.unwrap();
+    let result = mock_ref.execute_query(&sc, "getOwner", Vec::new());
+    assert_eq!(result.result_values, vec![owner.as_bytes().to_vec()]);
+}
+
+#[test]
+fn test_call_value_moved() {
+    let (mock_ref, owner) = new_mock();
+    let sc = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 30)).new_address.unwrap();
+    assert_eq!(mock_ref.get_balance(&owner), RustBigUint::from(70u32));
+    assert_eq!(mock_ref.get_balance(&sc), RustBigUint::from(30u32));
+
+    let mut tx = TxData::new_call("deposit", owner.clone(), sc.clone());
+    tx.set_call_value(20.into());
+    mock_ref.execute_tx(tx);
+    assert_eq!(mock_ref.get_balance(&owner), RustBigUint::from(50u32));
+    assert_eq!(mock_ref.get_balance(&sc), RustBigUint::from(50u32));
+    assert_eq!(mock_ref.execute_query(&sc, "getDeposits", Vec::new()).result_values, vec![vec![50]]);
+}
+
+#[test]
+fn test_insufficient_funds() {
+    let (mock_ref, owner) = new_mock();
+    let result = mock_ref.try_execute_tx(deploy_tx(&mock_ref, &owner, 101));
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"insufficient funds".to_vec());
+    assert!(result.new_address.is_none());
+    assert_eq!(mock_ref.get_balance(&owner), RustBigUint::from(100u32));
+
+    // the failed deploy is reverted, nonce included
+    let sc = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 60)).new_address.unwrap();
+    assert_eq!(sc, new_contract_address(&owner, 0));
+    let mut tx = TxData::new_call("deposit", owner.clone(), sc.clone());
+    tx.set_call_value(41.into());
+    let result = mock_ref.try_execute_tx(tx);
+    assert_eq!(result.result_status, 4);
+    assert_eq!(mock_ref.get_balance(&owner), RustBigUint::from(40u32));
+    assert_eq!(mock_ref.get_balance(&sc), RustBigUint::from(60u32));
+}
+
+#[test]
+fn test_add_typed_arg() {
+    let (mock_ref, owner) = new_mock();
+    let sc = mock_ref.execute_tx(deploy_tx(&mock_ref, &owner, 0)).new_address.unwrap();
+    let mut tx = TxData::new_call("sum", owner, sc);
+    tx.add_typed_arg(&300u64);
+    tx.add_typed_arg(&VarArgs::from(vec![4u64, 5u64]));
+    let result = mock_ref.execute_tx(tx);
+    assert_eq!(result.result_values, vec![vec![1, 53]]);
+}
+
+#[test]
+fn test_client_deploy_with_payment() {
+    let (mock_ref, owner) = new_mock();
+    let client = VaultClient::deploy(mock_ref.clone(), &owner, RustBigUint::from(25u32)).unwrap();
+    assert_eq!(client.address, new_contract_address(&owner, 0));
+    assert_eq!(client.get_owner(), Result::Ok(owner.clone()));
+    assert_eq!(client.get_deposits(), Result::Ok(RustBigUint::from(25u32)));
+    assert_eq!(mock_ref.get_balance(&client.address), RustBigUint::from(25u32));
+
+    let result = VaultClient::deploy(mock_ref.clone(), &owner, RustBigUint::from(76u32));
+    assert_eq!(result.err(), Some(b"insufficient funds".to_vec()));
+}
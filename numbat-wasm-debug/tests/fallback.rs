@@ -0,0 +1,74 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(WithFallbackImpl)]
+pub trait WithFallback {
+    #[init]
+    fn init(&self) {}
+
+    #[view(getReceived)]
+    #[storage_get("received")]
+    fn get_received(&self) -> BigUint;
+
+    #[storage_set("received")]
+    fn set_received(&self, received: &BigUint);
+
+    #[view(getFallbackCalls)]
+    #[storage_get("fallbackCalls")]
+    fn get_fallback_calls(&self) -> u32;
+
+    #[storage_set("fallbackCalls")]
+    fn set_fallback_calls(&self, fallback_calls: u32);
+
+    #[endpoint]
+    fn ping(&self) -> u32 {
+        1
+    }
+
+    #[fallback]
+    #[payable]
+    fn receive(&self, #[payment] payment: BigUint) {
+        self.set_received(&(self.get_received() + payment));
+        self.set_fallback_calls(self.get_fallback_calls() + 1);
+    }
+}
+
+fn deploy() -> WithFallbackClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    mock_ref.set_balance(&owner, 100.into());
+    WithFallbackClient::deploy(mock_ref, &owner).unwrap()
+}
+
+#[test]
+fn test_known_endpoint_not_routed_to_fallback() {
+    let sc = deploy();
+    assert_eq!(sc.ping(), Result::Ok(1));
+    assert_eq!(sc.get_fallback_calls(), Result::Ok(0));
+}
+
+#[test]
+fn test_unknown_function_routed_to_fallback() {
+    let sc = deploy();
+    let mut tx = TxData::new_call("unknown", sc.caller.clone(), sc.address.clone());
+    tx.add_arg(vec![1, 2, 3]);
+    tx.set_call_value(3.into());
+    let result = sc.caller_api.execute_tx(tx);
+    assert_eq!(result.result_status, 0);
+    assert_eq!(sc.get_fallback_calls(), Result::Ok(1));
+    assert_eq!(sc.get_received(), Result::Ok(RustBigUint::from(3u32)));
+}
+
+#[test]
+fn test_transfer_routed_to_fallback() {
+    let sc = deploy();
+    sc.caller_api.execute_tx(TxData::new_transfer(sc.caller.clone(), sc.address.clone(), 7.into()));
+    sc.caller_api.execute_tx(TxData::new_transfer(sc.caller.clone(), sc.address.clone(), 8.into()));
+    assert_eq!(sc.get_fallback_calls(), Result::Ok(2));
+    assert_eq!(sc.get_received(), Result::Ok(RustBigUint::from(15u32)));
+    assert_eq!(sc.caller_api.get_balance(&sc.caller), RustBigUint::from(85u32));
+    assert_eq!(sc.caller_api.get_balance(&sc.address), RustBigUint::from(15u32));
+}
@@ -0,0 +1,75 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(PricesImpl)]
+pub trait Prices {
+    #[init]
+    fn init(&self) {}
+
+    #[view(getPrice)]
+    #[storage_get("price")]
+    fn get_price(&self) -> FixedPoint<BigUint, 18>;
+
+    #[storage_set("price")]
+    fn store_price(&self, price: &FixedPoint<BigUint, 18>);
+
+    #[endpoint(setPrice)]
+    fn set_price(&self, price: FixedPoint<BigUint, 18>) -> FixedPoint<BigUint, 18> {
+        self.store_price(&price);
+        price
+    }
+
+    #[endpoint(addToPrice)]
+    fn add_to_price(&self, delta: FixedPoint<BigUint, 18>, times: u32) -> FixedPoint<BigUint, 18> {
+        let mut price = self.get_price();
+        for _ in 0..times {
+            price = price + delta.clone();
+        }
+        self.store_price(&price);
+        price
+    }
+}
+
+fn price(raw: u64) -> FixedPoint<RustBigUint, 18> {
+    FixedPoint::from_raw(RustBigUint::from(raw))
+}
+
+// 1.5, 0.25 and 1.75 with 18 decimals
+const ONE_AND_A_HALF: &[u8] = &[0x14, 0xd1, 0x12, 0x0d, 0x7b, 0x16, 0x00, 0x00];
+const A_QUARTER: &[u8] = &[0x03, 0x78, 0x2d, 0xac, 0xe9, 0xd9, 0x00, 0x00];
+const ONE_AND_THREE_QUARTERS: &[u8] = &[0x18, 0x49, 0x3f, 0xba, 0x64, 0xef, 0x00, 0x00];
+
+#[test]
+fn test_fixed_point_argument_result_and_storage() {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    let sc = mock_ref.execute_tx(TxData::new_deploy(Box::new(PricesImpl::new(mock_ref.clone())), owner.clone()))
+        .new_address.unwrap();
+
+    let mut tx = TxData::new_call("setPrice", owner.clone(), sc.clone());
+    tx.add_arg(ONE_AND_A_HALF.to_vec());
+    assert_eq!(mock_ref.execute_tx(tx).result_values, vec![ONE_AND_A_HALF.to_vec()]);
+    assert_eq!(mock_ref.execute_query(&sc, "getPrice", Vec::new()).result_values, vec![ONE_AND_A_HALF.to_vec()]);
+
+    let mut tx = TxData::new_call("addToPrice", owner, sc.clone());
+    tx.add_arg(A_QUARTER.to_vec());
+    tx.add_arg(vec![1]);
+    assert_eq!(mock_ref.execute_tx(tx).result_values, vec![ONE_AND_THREE_QUARTERS.to_vec()]);
+
+    mock_ref.set_dummy_tx(&sc);
+    assert_eq!(mock_ref.storage_load(&b"price"[..]), ONE_AND_THREE_QUARTERS.to_vec());
+}
+
+#[test]
+fn test_fixed_point_client() {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    let client = PricesClient::deploy(mock_ref, &owner).unwrap();
+    assert_eq!(client.get_price(), Result::Ok(price(0)));
+    assert_eq!(client.set_price(price(1_500_000_000_000_000_000)), Result::Ok(price(1_500_000_000_000_000_000)));
+    assert_eq!(client.add_to_price(price(250_000_000_000_000_000), 2), Result::Ok(price(2_000_000_000_000_000_000)));
+    assert_eq!(client.get_price(), Result::Ok(price(2_000_000_000_000_000_000)));
+}
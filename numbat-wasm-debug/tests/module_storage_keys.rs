@@ -0,0 +1,126 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+mod counter {
+    imports!();
+
+    #[numbat_wasm_derive::module(CounterModuleImpl)]
+    pub trait CounterModule {
+        #[storage_get("count")]
+        fn get_count(&self) -> u32;
+
+        #[storage_set("count")]
+        fn set_count(&self, count: u32);
+    }
+}
+
+mod pause {
+    imports!();
+
+    #[numbat_wasm_derive::module(PauseModuleImpl)]
+    pub trait PauseModule {
+        #[view(isPaused)]
+        #[storage_get("paused")]
+        fn is_paused(&self) -> bool;
+
+        #[endpoint(pause)]
+        #[storage_set("paused")]
+        fn set_paused(&self, paused: bool);
+    }
+}
+
+mod fee {
+    imports!();
+    use super::counter::CounterModuleImpl;
+
+    #[numbat_wasm_derive::module(FeeModuleImpl)]
+    pub trait FeeModule {
+        #[storage_get("fee")]
+        fn get_fee(&self, token: u32) -> u64;
+
+        #[module(CounterModuleImpl)]
+        #[storage_prefix("cnt.")]
+        fn counter(&self) -> CounterModuleImpl<T, BigInt, BigUint>;
+    }
+}
+
+use fee::{FeeModule, FeeModuleImpl};
+use counter::CounterModule;
+use pause::{PauseModule, PauseModuleImpl};
+
+#[numbat_wasm_derive::contract(ModuleStorageKeysImpl)]
+pub trait ModuleStorageKeys {
+    #[init]
+    fn init(&self) {}
+
+    #[view(getOwner)]
+    #[storage_get("owner")]
+    fn get_owner(&self) -> Address;
+
+    #[module(PauseModuleImpl)]
+    fn pause(&self) -> PauseModuleImpl<T, BigInt, BigUint>;
+
+    #[module(FeeModuleImpl)]
+    #[storage_prefix("a.")]
+    fn fees(&self) -> FeeModuleImpl<T, BigInt, BigUint>;
+
+    #[module(FeeModuleImpl)]
+    #[storage_prefix("b.")]
+    fn other_fees(&self) -> FeeModuleImpl<T, BigInt, BigUint>;
+}
+
+#[test]
+fn test_storage_keys_include_nested_modules() {
+    assert_eq!(counter::CounterModuleImpl.own, &[("count", false)]);
+    assert!(counter::CounterModuleImpl.modules.is_empty());
+
+    assert_eq!(FeeModuleImpl.own, &[("fee", true)]);
+    assert_eq!(FeeModuleImpl.modules.len(), 1);
+    assert_eq!(FeeModuleImpl.modules[0].0, "cnt.");
+    assert_eq!(FeeModuleImpl.modules[0].1.own, &[("count", false)]);
+
+    assert_eq!(ModuleStorageKeysImpl.own, &[("owner", false)]);
+    let prefixes: Vec<&str> = ModuleStorageKeysImpl.modules.iter().map(|(prefix, _)| *prefix).collect();
+    assert_eq!(prefixes, vec!["", "a.", "b."]);
+}
+
+#[test]
+fn test_storage_keys_include_endpoint_names() {
+    assert_eq!(pause::PauseModuleImpl.endpoints, &["isPaused", "pause"]);
+    assert!(FeeModuleImpl.endpoints.is_empty());
+    assert_eq!(ModuleStorageKeysImpl.endpoints, &["getOwner", "init"]);
+}
+
+#[test]
+fn test_module_endpoint_name_collisions() {
+    use numbat_wasm::storage::*;
+    assert!(endpoint_name_used_by_any("pause", &pause::PauseModuleImpl));
+    assert!(!endpoint_name_used_by_any("getOwner", &pause::PauseModuleImpl));
+    assert!(endpoint_name_sets_overlap(&ModuleStorageKeysImpl, &pause::PauseModuleImpl));
+    assert!(!endpoint_name_sets_overlap(&FeeModuleImpl, &pause::PauseModuleImpl));
+}
+
+#[test]
+fn test_nested_module_storage_key_collisions() {
+    use numbat_wasm::storage::*;
+    assert!(storage_key_collides_with_any(("a.cnt.count", false), "a.", &FeeModuleImpl));
+    assert!(storage_key_collides_with_any(("a.cnt", true), "a.", &FeeModuleImpl));
+    assert!(!storage_key_collides_with_any(("a.count", false), "a.", &FeeModuleImpl));
+    assert!(storage_key_sets_collide("", &ModuleStorageKeysImpl, "a.cnt.", &counter::CounterModuleImpl));
+}
+
+#[test]
+fn test_contract_with_nested_modules() {
+    let mock_ref = AndesMockState::new_ref_with_account(&Address::zero());
+    let contract = ModuleStorageKeysImpl::new(mock_ref.clone());
+    contract.fees().counter().set_count(3);
+    contract.other_fees().counter().set_count(4);
+    assert_eq!(contract.fees().counter().get_count(), 3);
+    assert_eq!(contract.other_fees().counter().get_count(), 4);
+    assert_eq!(mock_ref.storage_load(&b"a.cnt.count"[..]), vec![3]);
+    contract.pause().set_paused(true);
+    assert!(contract.pause().is_paused());
+}
@@ -0,0 +1,59 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+use adder::{AdderClient, AdderProxy};
+
+#[numbat_wasm_derive::contract(CallerImpl)]
+pub trait Caller {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(callAdd)]
+    fn call_add(&self, adder_address: Address, value: u64) {
+        contract_proxy!(self, &adder_address, AdderProxy)
+            .add(value)
+            .execute(self);
+    }
+
+    #[payable]
+    #[endpoint(callAddPayment)]
+    fn call_add_payment(&self, #[payment] payment: BigUint, adder_address: Address, times: u32) {
+        contract_proxy!(self, &adder_address, AdderProxy)
+            .add_payment(payment, times)
+            .execute(self);
+    }
+}
+
+fn deploy() -> (CallerClient<AndesMockRef>, Address) {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    mock_ref.set_balance(&owner, 100.into());
+    let adder = AdderClient::deploy(mock_ref.clone(), &owner, 10).unwrap();
+    (CallerClient::deploy(mock_ref, &owner).unwrap(), adder.address)
+}
+
+#[test]
+fn test_proxy_call() {
+    let (caller, adder) = deploy();
+    caller.call_add(adder.clone(), 5).unwrap();
+
+    let async_calls = caller.caller_api.take_async_calls();
+    assert_eq!(async_calls.len(), 1);
+    assert_eq!(async_calls[0].from, caller.address);
+    assert_eq!(async_calls[0].to, adder);
+    assert_eq!(async_calls[0].amount, 0.into());
+    assert_eq!(async_calls[0].data, b"add@05".to_vec());
+}
+
+#[test]
+fn test_proxy_call_with_payment() {
+    let (caller, adder) = deploy();
+    caller.call_add_payment(RustBigUint::from(7u32), adder, 3).unwrap();
+
+    let async_calls = caller.caller_api.take_async_calls();
+    assert_eq!(async_calls.len(), 1);
+    assert_eq!(async_calls[0].amount, 7.into());
+    assert_eq!(async_calls[0].data, b"addPayment@03".to_vec());
+}
@@ -0,0 +1,76 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(LimitsImpl)]
+pub trait Limits {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(setLimit)]
+    #[storage_set_opt("limit")]
+    fn set_limit(&self, id: u32, limit: u64);
+
+    #[storage_get_opt("limit")]
+    fn get_limit_opt(&self, id: u32) -> Option<u64>;
+
+    #[view(hasNoLimit)]
+    #[storage_is_empty("limit")]
+    fn has_no_limit(&self, id: u32) -> bool;
+
+    #[endpoint(clearLimit)]
+    #[storage_clear("limit")]
+    fn clear_limit(&self, id: u32);
+
+    /// Returns the limit, or the given default if none was set.
+    #[view(getLimitOr)]
+    fn get_limit_or(&self, id: u32, default: u64) -> u64 {
+        self.get_limit_opt(id).unwrap_or(default)
+    }
+}
+
+fn deploy() -> LimitsClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    LimitsClient::deploy(AndesMockState::new_ref_with_account(&owner), &owner).unwrap()
+}
+
+#[test]
+fn test_storage_get_opt() {
+    let sc = deploy();
+    assert_eq!(sc.get_limit_or(1, 99), Result::Ok(99));
+
+    assert_eq!(sc.set_limit(1, 5), Result::Ok(()));
+    assert_eq!(sc.get_limit_or(1, 99), Result::Ok(5));
+
+    // zero is stored with a presence byte, so it is not mistaken for a missing limit
+    assert_eq!(sc.set_limit(1, 0), Result::Ok(()));
+    assert_eq!(sc.get_limit_or(1, 99), Result::Ok(0));
+    assert_eq!(sc.get_limit_or(2, 99), Result::Ok(99));
+}
+
+#[test]
+fn test_storage_is_empty() {
+    let sc = deploy();
+    assert_eq!(sc.has_no_limit(1), Result::Ok(true));
+
+    assert_eq!(sc.set_limit(1, 0), Result::Ok(()));
+    assert_eq!(sc.has_no_limit(1), Result::Ok(false));
+    assert_eq!(sc.has_no_limit(2), Result::Ok(true));
+}
+
+#[test]
+fn test_storage_clear() {
+    let sc = deploy();
+    assert_eq!(sc.set_limit(1, 0), Result::Ok(()));
+    assert_eq!(sc.set_limit(2, 7), Result::Ok(()));
+
+    assert_eq!(sc.clear_limit(1), Result::Ok(()));
+    assert_eq!(sc.has_no_limit(1), Result::Ok(true));
+    assert_eq!(sc.get_limit_or(1, 99), Result::Ok(99));
+
+    assert_eq!(sc.clear_limit(2), Result::Ok(()));
+    assert_eq!(sc.has_no_limit(2), Result::Ok(true));
+    assert_eq!(sc.get_limit_or(2, 99), Result::Ok(99));
+}
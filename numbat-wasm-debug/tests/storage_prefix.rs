@@ -0,0 +1,134 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+mod ownable {
+    imports!();
+
+    #[numbat_wasm_derive::module(OwnableModuleImpl)]
+    pub trait OwnableModule {
+        #[storage_get("owner")]
+        fn get_owner(&self) -> u32;
+
+        #[storage_set("owner")]
+        fn set_owner(&self, owner: u32);
+
+        #[storage_get_mut("changes")]
+        fn changes(&self) -> mut_storage!(u32);
+
+        #[storage_get_opt("limit")]
+        fn get_limit_opt(&self, id: u32) -> Option<u64>;
+
+        #[storage_set_opt("limit")]
+        fn set_limit(&self, id: u32, limit: u64);
+
+        fn change_owner(&self, owner: u32) {
+            self.set_owner(owner);
+            *self.changes() += 1;
+        }
+    }
+}
+
+mod pause {
+    imports!();
+
+    #[numbat_wasm_derive::module(PauseModuleImpl)]
+    pub trait PauseModule {
+        #[view(isPaused)]
+        #[storage_get("paused")]
+        fn is_paused(&self) -> bool;
+
+        #[storage_set("paused")]
+        fn set_paused(&self, paused: bool);
+    }
+}
+
+use ownable::{OwnableModule, OwnableModuleImpl};
+use pause::{PauseModule, PauseModuleImpl};
+
+#[numbat_wasm_derive::contract(StoragePrefixImpl)]
+pub trait StoragePrefix {
+    #[init]
+    fn init(&self) {}
+
+    #[storage_get("owner")]
+    fn get_owner(&self) -> u32;
+
+    #[storage_set("owner")]
+    fn set_owner(&self, owner: u32);
+
+    #[module(OwnableModuleImpl)]
+    #[storage_prefix("a.")]
+    fn first(&self) -> OwnableModuleImpl<T, BigInt, BigUint>;
+
+    #[module(OwnableModuleImpl)]
+    #[storage_prefix("b.")]
+    fn second(&self) -> OwnableModuleImpl<T, BigInt, BigUint>;
+
+    // modules with endpoints cannot have a prefix
+    #[module(PauseModuleImpl)]
+    fn pause(&self) -> PauseModuleImpl<T, BigInt, BigUint>;
+}
+
+fn new_contract() -> (AndesMockRef, StoragePrefixImpl<AndesMockRef, RustBigInt, RustBigUint>) {
+    let mock_ref = AndesMockState::new_ref_with_account(&Address::zero());
+    let contract = StoragePrefixImpl::new(mock_ref.clone());
+    (mock_ref, contract)
+}
+
+#[test]
+fn test_module_instances_do_not_clobber_each_other() {
+    let (mock_ref, contract) = new_contract();
+    contract.set_owner(1);
+    contract.first().change_owner(2);
+    contract.second().change_owner(3);
+    contract.second().change_owner(4);
+
+    assert_eq!(contract.get_owner(), 1);
+    assert_eq!(contract.first().get_owner(), 2);
+    assert_eq!(contract.second().get_owner(), 4);
+    assert_eq!(mock_ref.storage_load(&b"owner"[..]), vec![1]);
+    assert_eq!(mock_ref.storage_load(&b"a.owner"[..]), vec![2]);
+    assert_eq!(mock_ref.storage_load(&b"b.owner"[..]), vec![4]);
+}
+
+#[test]
+fn test_prefixed_borrowed_storage() {
+    let (mock_ref, contract) = new_contract();
+    contract.first().change_owner(2);
+    contract.second().change_owner(3);
+    contract.second().change_owner(4);
+
+    assert_eq!(*contract.first().changes(), 1);
+    assert_eq!(*contract.second().changes(), 2);
+    assert_eq!(mock_ref.storage_load(&b"a.changes"[..]), vec![1]);
+    assert_eq!(mock_ref.storage_load(&b"b.changes"[..]), vec![2]);
+    assert!(mock_ref.storage_load(&b"changes"[..]).is_empty());
+}
+
+#[test]
+fn test_prefixed_keys_with_arguments() {
+    let (mock_ref, contract) = new_contract();
+    contract.first().set_limit(7, 0);
+
+    assert_eq!(contract.first().get_limit_opt(7), Some(0));
+    assert_eq!(contract.second().get_limit_opt(7), None);
+    assert_eq!(mock_ref.storage_load(&b"a.limit\x00\x00\x00\x07"[..]), vec![1, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_module_endpoint_storage() {
+    let (mock_ref, contract) = new_contract();
+    contract.pause().set_paused(true);
+
+    // the exported endpoints of a module run on an instance without any prefix,
+    // which sees the same storage as the instance of the contract
+    let exported = PauseModuleImpl::new(mock_ref.clone());
+    assert!(exported.is_paused());
+    assert_eq!(mock_ref.storage_load(&b"paused"[..]), vec![1]);
+
+    assert!(numbat_wasm::storage::has_endpoints(&pause::PauseModuleImpl));
+    assert!(!numbat_wasm::storage::has_endpoints(&ownable::OwnableModuleImpl));
+}
@@ -0,0 +1,49 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(StorageUpdateImpl)]
+pub trait StorageUpdate {
+    #[init]
+    fn init(&self) {}
+
+    #[view(getScore)]
+    #[storage_get("score")]
+    fn get_score(&self, id: u32) -> u64;
+
+    #[storage_update("score")]
+    fn update_score<R, F: FnOnce(&mut u64) -> R>(&self, value: u32, key: F) -> R;
+
+    #[storage_update("total")]
+    fn update_total(&self, value: impl FnMut(&mut u32));
+
+    #[view(getTotal)]
+    #[storage_get("total")]
+    fn get_total(&self) -> u32;
+
+    #[endpoint(addScore)]
+    fn add_score(&self, id: u32, amount: u64) -> u64 {
+        self.update_total(|total| *total += 1);
+        self.update_score(id, |score| {
+            *score += amount;
+            *score
+        })
+    }
+}
+
+fn deploy() -> StorageUpdateClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    StorageUpdateClient::deploy(AndesMockState::new_ref_with_account(&owner), &owner).unwrap()
+}
+
+#[test]
+fn test_storage_update() {
+    let sc = deploy();
+    assert_eq!(sc.add_score(7, 10), Result::Ok(10));
+    assert_eq!(sc.add_score(7, 10), Result::Ok(20));
+    assert_eq!(sc.get_score(7), Result::Ok(20));
+    assert_eq!(sc.get_score(8), Result::Ok(0));
+    assert_eq!(sc.get_total(), Result::Ok(2));
+}
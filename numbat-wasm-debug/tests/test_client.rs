@@ -0,0 +1,60 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(CounterImpl)]
+pub trait Counter {
+    #[init]
+    fn init(&self, initial_value: BigUint) {
+        self.set_value(&initial_value);
+    }
+
+    #[view(getValue)]
+    #[storage_get("value")]
+    fn get_value(&self) -> BigUint;
+
+    #[storage_set("value")]
+    fn set_value(&self, value: &BigUint);
+
+    #[endpoint]
+    fn add(&self, amount: &BigUint) -> SCResult<BigUint> {
+        let value = self.get_value() + amount.clone();
+        self.set_value(&value);
+        if *amount == 0 {
+            return sc_error!("zero amount");
+        }
+        Ok(value)
+    }
+}
+
+fn deploy() -> (AndesMockRef, CounterClient<AndesMockRef>) {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    let client = CounterClient::deploy(mock_ref.clone(), &owner, RustBigUint::from(5u32)).unwrap();
+    (mock_ref, client)
+}
+
+#[test]
+fn test_client_deploy() {
+    let (_, client) = deploy();
+    assert_eq!(client.get_value(), Result::Ok(RustBigUint::from(5u32)));
+}
+
+#[test]
+fn test_client_call_and_view() {
+    let (_, client) = deploy();
+    assert_eq!(client.add(&RustBigUint::from(3u32)), Result::Ok(RustBigUint::from(8u32)));
+    assert_eq!(client.add(&RustBigUint::from(300u32)), Result::Ok(RustBigUint::from(308u32)));
+    assert_eq!(client.get_value(), Result::Ok(RustBigUint::from(308u32)));
+}
+
+#[test]
+fn test_client_error_reverts() {
+    let (mock_ref, client) = deploy();
+    assert_eq!(client.add(&RustBigUint::from(0u32)), Result::Err(b"zero amount".to_vec()));
+    assert_eq!(client.get_value(), Result::Ok(RustBigUint::from(5u32)));
+    let result = mock_ref.execute_query(&client.address, "getValue", Vec::new());
+    assert_eq!(result.result_values, vec![vec![5]]);
+}
@@ -0,0 +1,89 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::callable(ReceiverProxy)]
+pub trait Receiver {
+    #[callback(receive_callback)]
+    fn receive(&self, #[callback_arg] amount: u32);
+}
+
+#[numbat_wasm_derive::contract(NoFallbackImpl)]
+pub trait NoFallback {
+    #[init]
+    fn init(&self) {}
+
+    #[view(getTotal)]
+    #[storage_get("total")]
+    fn get_total(&self) -> u32;
+
+    #[storage_set("total")]
+    fn set_total(&self, total: u32);
+
+    #[endpoint]
+    fn send(&self, to: Address, amount: u32) {
+        let proxy = contract_proxy!(self, &to, Receiver);
+        proxy.receive(amount);
+    }
+
+    #[callback]
+    fn receive_callback(&self, result: AsyncCallResult<()>, #[callback_arg] amount: u32) {
+        if let AsyncCallResult::Ok(()) = result {
+            self.set_total(self.get_total() + amount);
+        }
+    }
+}
+
+fn deploy() -> NoFallbackClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    let mock_ref = AndesMockState::new_ref_with_account(&owner);
+    mock_ref.set_balance(&owner, 100.into());
+    NoFallbackClient::deploy(mock_ref, &owner).unwrap()
+}
+
+#[test]
+fn test_unknown_function() {
+    let sc = deploy();
+    let mut tx = TxData::new_call("unknown", sc.caller.clone(), sc.address.clone());
+    tx.add_arg(vec![1]);
+    let result = sc.caller_api.try_execute_tx(tx);
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"invalid function (not found)".to_vec());
+}
+
+#[test]
+fn test_transfer_without_fallback() {
+    let sc = deploy();
+    let result = sc.caller_api.try_execute_tx(TxData::new_transfer(sc.caller.clone(), sc.address.clone(), 10.into()));
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"invalid function (not found)".to_vec());
+    assert_eq!(sc.caller_api.get_balance(&sc.caller), RustBigUint::from(100u32));
+}
+
+#[test]
+fn test_try_execute_callback() {
+    let sc = deploy();
+    let receiver = Address::from([3u8; 32]);
+    for (i, amount) in [5u8, 7u8].iter().enumerate() {
+        let mut tx = TxData::new_call("send", sc.caller.clone(), sc.address.clone());
+        tx.add_arg(receiver.as_bytes().to_vec());
+        tx.add_arg(vec![*amount]);
+        // the callback closures are stored per tx hash
+        tx.set_tx_hash(H256::from([i as u8 + 1; 32]));
+        sc.caller_api.execute_tx(tx);
+    }
+    let async_calls = sc.caller_api.take_async_calls();
+    assert_eq!(async_calls.len(), 2);
+
+    let result = sc.caller_api.try_execute_callback(&async_calls[0], vec![vec![]]);
+    assert_eq!(result.result_status, 0);
+    assert_eq!(sc.get_total(), Result::Ok(5));
+
+    // the extra result is only detected after the callback ran, so its storage change is reverted
+    let result = sc.caller_api.try_execute_callback(&async_calls[1], vec![vec![], vec![1]]);
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"wrong number of arguments".to_vec());
+    assert_eq!(sc.get_total(), Result::Ok(5));
+}
@@ -0,0 +1,94 @@
+#![allow(unused_attributes)]
+
+imports!();
+
+use numbat_wasm_debug::*;
+
+#[numbat_wasm_derive::contract(ViewsImpl)]
+pub trait Views {
+    #[init]
+    fn init(&self, counter: u32) {
+        self.set_counter(counter);
+    }
+
+    #[view(getCounter)]
+    #[storage_get("counter")]
+    fn get_counter(&self) -> u32;
+
+    #[storage_set("counter")]
+    fn set_counter(&self, counter: u32);
+
+    #[endpoint]
+    fn increment(&self) {
+        self.set_counter(self.get_counter() + 1);
+    }
+
+    #[view(sneakyIncrement)]
+    fn sneaky_increment(&self) -> u32 {
+        self.increment_counter()
+    }
+
+    #[view(checkedCounter)]
+    fn checked_counter(&self) -> SCResult<u32> {
+        let counter = self.get_counter();
+        if counter == 0 {
+            return sc_error!("counter is zero");
+        }
+        Ok(counter)
+    }
+
+    fn increment_counter(&self) -> u32 {
+        let counter = self.get_counter() + 1;
+        self.set_counter(counter);
+        counter
+    }
+}
+
+fn deploy(counter: u32) -> ViewsClient<AndesMockRef> {
+    let owner = Address::from([1u8; 32]);
+    ViewsClient::deploy(AndesMockState::new_ref_with_account(&owner), &owner, counter).unwrap()
+}
+
+#[test]
+fn test_is_view() {
+    let contract = ViewsImpl::new(AndesMockState::new_ref());
+    assert!(contract.is_view("getCounter"));
+    assert!(contract.is_view("sneakyIncrement"));
+    assert!(!contract.is_view("increment"));
+    assert!(!contract.is_view("init"));
+    assert!(!contract.is_view("unknown"));
+}
+
+#[test]
+fn test_query_view() {
+    let views = deploy(5);
+    views.increment().unwrap();
+    let result = views.caller_api.execute_query(&views.address, "getCounter", Vec::new());
+    assert_eq!(result.result_status, 0);
+    assert_eq!(result.result_values, vec![vec![6]]);
+}
+
+#[test]
+fn test_query_view_error() {
+    let views = deploy(0);
+    let result = views.caller_api.execute_query(&views.address, "checkedCounter", Vec::new());
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"counter is zero".to_vec());
+}
+
+#[test]
+#[should_panic(expected = "`increment` is not a view, it can only be called in a transaction")]
+fn test_query_non_view() {
+    let views = deploy(5);
+    views.caller_api.execute_query(&views.address, "increment", Vec::new());
+}
+
+#[test]
+fn test_view_state_change_rejected() {
+    let views = deploy(5);
+    assert_eq!(views.sneaky_increment(), Result::Err(b"views cannot change state".to_vec()));
+    let result = views.caller_api.execute_query(&views.address, "sneakyIncrement", Vec::new());
+    assert_eq!(result.result_status, 4);
+    assert_eq!(result.result_message, b"views cannot change state".to_vec());
+    assert_eq!(views.get_counter(), Result::Ok(5));
+}
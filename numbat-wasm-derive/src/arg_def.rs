@@ -25,3 +25,16 @@ pub fn generate_arg_call_name(arg: &MethodArg) -> proc_macro2::TokenStream {
         _  => quote!{ #pat },
     }
 }
+
+/// `&[u8]` and `&str` arguments are loaded borrowed, from a buffer that fits them,
+/// instead of being decoded to an owned value first.
+pub fn is_borrowed_arg(arg: &MethodArg) -> bool {
+    match &arg.ty {
+        syn::Type::Reference(type_reference) => match &*type_reference.elem {
+            syn::Type::Slice(type_slice) => matches!(&*type_slice.elem, syn::Type::Path(type_path) if type_path.path.is_ident("u8")),
+            syn::Type::Path(type_path) => type_path.path.is_ident("str"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
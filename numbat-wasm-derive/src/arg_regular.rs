@@ -24,6 +24,22 @@ pub fn generate_load_single_arg(arg: &MethodArg, arg_index_expr: &proc_macro2::T
     }
 }
 
+fn arg_buffer_ident(arg: &MethodArg) -> syn::Ident {
+    format_ident!("___arg_buffer_{}", arg.index as usize)
+}
+
+/// Statements loading a `&[u8]` or `&str` argument, that borrows from a buffer declared just before it.
+pub fn generate_load_single_arg_borrowed(arg: &MethodArg, arg_index_expr: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let pat = &arg.pat;
+    let arg_ty = &arg.ty;
+    let arg_name_literal = pat_literal(pat);
+    let buffer_ident = arg_buffer_ident(arg);
+    quote! {
+        let mut #buffer_ident = numbat_wasm::Vec::new();
+        let #pat: #arg_ty = numbat_wasm::load_single_arg_borrowed(&self.api, #arg_index_expr, &mut #buffer_ident, #arg_name_literal);
+    }
+}
+
 pub fn generate_load_dyn_arg(arg: &MethodArg,
         loader_expr: &proc_macro2::TokenStream,
         err_handler_expr: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
@@ -31,6 +47,13 @@ pub fn generate_load_dyn_arg(arg: &MethodArg,
     let pat = &arg.pat;
     let arg_ty = &arg.ty;
     let arg_name_literal = pat_literal(pat);
+    if is_borrowed_arg(arg) {
+        let buffer_ident = arg_buffer_ident(arg);
+        return quote! {
+            let mut #buffer_ident = numbat_wasm::next_arg_buffer(#loader_expr);
+            let #pat: #arg_ty = numbat_wasm::load_dyn_arg_borrowed(#loader_expr, &mut #buffer_ident, #err_handler_expr, #arg_name_literal);
+        };
+    }
     match &arg.ty {
         syn::Type::Reference(type_reference) => {
             if type_reference.mutability.is_some() {
@@ -20,8 +20,10 @@ pub fn arg_serialize_push(
             if type_reference.mutability.is_some() {
                 panic!("Mutable references not supported as contract method arguments");
             }
+            // slices and strings are not sized, so they get encoded through their reference
+            let var_ref = if is_borrowed_arg(arg) { quote!{ &#var_name } } else { var_name };
             quote!{
-                if let Result::Err(sc_err) = AsynCallArg::push_async_arg(#var_name, &mut #arg_accumulator) {
+                if let Result::Err(sc_err) = AsynCallArg::push_async_arg(#var_ref, &mut #arg_accumulator) {
                     self.api.signal_error(sc_err.as_bytes());
                 }
             }
@@ -59,3 +61,57 @@ pub fn arg_serialize_push_multi(
         other_arg => panic!("Unsupported argument type: {:?}, neither path nor reference", other_arg)
     }
 }
+
+/// Serializes an argument of an outgoing call, into the callback data if it is a `#[callback_arg]`.
+/// `expected_count_expr` is given for `#[multi]` arguments.
+pub fn call_data_push(arg: &MethodArg, expected_count_expr: Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    let arg_accumulator = if arg.is_callback_arg {
+        quote! { callback_data_ser }
+    } else {
+        quote! { call_data_ser }
+    };
+    match expected_count_expr {
+        Some(count_expr) => arg_serialize_push_multi(arg, &arg_accumulator, &count_expr),
+        None => arg_serialize_push(arg, &arg_accumulator),
+    }
+}
+
+/// Arguments of an outgoing call, as used by proxies and test clients.
+pub struct CallArgs {
+    /// One snippet per serialized argument.
+    pub push_snippets: Vec<proc_macro2::TokenStream>,
+    /// The owned value of the `#[payment]` argument, if there is one.
+    pub payment: Option<proc_macro2::TokenStream>,
+}
+
+/// The `#[payment]` argument becomes the call value, the others get serialized by `push_snippet`,
+/// which also gets the expected count of `#[multi]` arguments.
+pub fn call_args<F>(method_args: &[MethodArg], push_snippet: F) -> CallArgs
+where
+    F: Fn(&MethodArg, Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream,
+{
+    let mut payment = None;
+    let mut push_snippets = Vec::new();
+    for arg in method_args.iter() {
+        match &arg.metadata {
+            ArgMetadata::Single | ArgMetadata::VarArgs => {
+                push_snippets.push(push_snippet(arg, None));
+            },
+            ArgMetadata::Payment => {
+                if payment.is_some() {
+                    panic!("Only one payment argument allowed in outgoing calls");
+                }
+                let pat = &arg.pat;
+                payment = Some(match &arg.ty {
+                    syn::Type::Reference(_) => quote! { #pat.clone() },
+                    _ => quote! { #pat },
+                });
+            },
+            ArgMetadata::Multi(multi_attr) => {
+                let count_expr = &multi_attr.count_expr;
+                push_snippets.push(push_snippet(arg, Some(quote! { #count_expr as usize })));
+            },
+        }
+    }
+    CallArgs { push_snippets, payment }
+}
@@ -82,52 +82,19 @@ impl Callable {
         self.methods.iter().map(|m| {
             let msig = m.generate_sig();
 
-            let mut payment_count = 0;
-            let arg_push_snippets: Vec<proc_macro2::TokenStream> = 
-                m.method_args
-                    .iter()
-                    .map(|arg| {
-                        let arg_accumulator = if arg.is_callback_arg {
-                            quote! { callback_data_ser }
-                        } else {
-                            quote! { call_data_ser }
-                        };
+            let CallArgs { push_snippets: arg_push_snippets, payment } = call_args(&m.method_args, call_data_push);
+            let amount_expr = payment.unwrap_or_else(|| quote! { BigUint::zero() });
 
-                        match &arg.metadata {
-                            ArgMetadata::Single | ArgMetadata::VarArgs => {
-                                arg_serialize_push(arg, &arg_accumulator)
-                            },
-                            ArgMetadata::Payment => {
-                                // #[payment]
-                                payment_count += 1;
-                                let pat = &arg.pat;
-                                quote! { let amount = #pat; }
-                            },
-                            ArgMetadata::Multi(multi_attr) => {
-                                // #[multi(...)]
-                                let count_expr = &multi_attr.count_expr;
-                                arg_serialize_push_multi(arg, &arg_accumulator, &quote!{ #count_expr as usize })
-                            },
-                        }
-                    })
-                    .collect();
-
-            let amount_snippet = match payment_count {
-                0 => quote! { let amount = BigUint::zero(); },
-                1 => quote! {},
-                _ => panic!("Only one payment argument allowed in call proxy")
-            };
-
-            let (callback_init, callback_store) = if let Some(callback_ident) = &m.callback {
+            let (callback_init, callback_attach) = if let Some(callback_ident) = &m.callback {
                 let cb_name_str = &callback_ident.arg.to_string();
                 let cb_name_literal = array_literal(cb_name_str.as_bytes());
                 let callback_init = quote! {
                     let mut callback_data_ser = numbat_wasm::call_data::CallDataSerializer::new( & #cb_name_literal );
                 };
-                let callback_store = quote! {
-                    self.api.storage_store(&self.api.get_tx_hash().as_ref(), callback_data_ser.as_slice());
+                let callback_attach = quote! {
+                    let async_call = async_call.with_callback(numbat_wasm::CallbackClosure::from(callback_data_ser));
                 };
-                (callback_init, callback_store)
+                (callback_init, callback_attach)
             } else {
                 (quote! {}, quote! {})
             };
@@ -135,12 +102,14 @@ impl Callable {
             let m_name_literal = array_literal(m.name.to_string().as_bytes());
             let sig = quote! {
                 #msig {
-                    #amount_snippet
+                    let ___amount___ = #amount_expr;
                     let mut call_data_ser = numbat_wasm::call_data::CallDataSerializer::new( & #m_name_literal );
                     #callback_init
                     #(#arg_push_snippets)*
-                    #callback_store
-                    self.api.async_call(&self.address, &amount, call_data_ser.as_slice());
+                    let async_call = numbat_wasm::AsyncCall::from_call_data(&self.address, call_data_ser)
+                        .with_value(___amount___);
+                    #callback_attach
+                    async_call.execute(&self.api);
                 }
             };
             sig
@@ -4,6 +4,11 @@ use super::contract_gen_storage::*;
 use super::contract_gen_method::*;
 use super::contract_gen_module::*;
 use super::contract_gen_callback::*;
+use super::contract_gen_collisions::*;
+use super::contract_gen_label::*;
+use super::contract_gen_view::*;
+use super::contract_gen_proxy::*;
+use super::contract_gen_test_client::*;
 use super::snippets;
 use super::util::*;
 
@@ -11,11 +16,16 @@ pub struct Contract {
     pub trait_name: proc_macro2::Ident,
     pub contract_impl_name: syn::Path,
     pub supertrait_paths: Vec<syn::Path>,
+    /// Modules keep a storage prefix, set by the contract or module that includes them.
+    pub is_module: bool,
+    /// Name of the proxy trait to generate for calling other instances of the contract, if requested.
+    pub proxy_name: Option<syn::Ident>,
     methods: Vec<Method>,
 }
 
 impl Contract {
     pub fn new(args: syn::AttributeArgs, contract_trait: &syn::ItemTrait) -> Self {
+        let (proxy_name, args) = extract_proxy_name(args);
         let contract_impl_name = extract_struct_name(args);
         
         let supertrait_paths: Vec<syn::Path> = contract_trait
@@ -36,14 +46,37 @@ impl Contract {
             })
             .collect();
 
+        check_views_read_only(&methods);
+        check_endpoint_names_unique(&methods);
+        check_storage_key_collisions(&methods);
+        if methods.iter().filter(|m| m.is_fallback).count() > 1 {
+            panic!("Only one fallback allowed per contract.");
+        }
+
         Contract {
             trait_name: contract_trait.ident.clone(),
             contract_impl_name,
             supertrait_paths,
+            is_module: false,
+            proxy_name,
             methods,
         }
     }
 
+    pub fn new_module(args: syn::AttributeArgs, module_trait: &syn::ItemTrait) -> Self {
+        let contract = Contract::new(args, module_trait);
+        if contract.proxy_name.is_some() {
+            panic!("Modules cannot have proxies, only contracts.");
+        }
+        if contract.methods.iter().any(|m| m.is_fallback) {
+            panic!("Modules cannot have a fallback, only contracts.");
+        }
+        Contract {
+            is_module: true,
+            ..contract
+        }
+    }
+
     pub fn extract_pub_method_sigs(&self) -> Vec<proc_macro2::TokenStream> {
         self.methods.iter()
             .filter_map(|m| {
@@ -99,6 +132,11 @@ impl Contract {
                     MethodMetadata::StorageGetter{ .. } |
                     MethodMetadata::StorageSetter{ .. } |
                     MethodMetadata::StorageGetMut{ .. } |
+                    MethodMetadata::StorageGetOpt{ .. } |
+                    MethodMetadata::StorageSetOpt{ .. } |
+                    MethodMetadata::StorageIsEmpty{ .. } |
+                    MethodMetadata::StorageClear{ .. } |
+                    MethodMetadata::StorageUpdate{ .. } |
                     MethodMetadata::Module{ .. } => {
                         let sig = m.generate_sig();
                         Some(quote! { #sig ; })
@@ -117,13 +155,23 @@ impl Contract {
                     MethodMetadata::Event{ identifier } => 
                         Some(generate_event_impl(&m, identifier.clone())),
                     MethodMetadata::StorageGetter{ visibility: _, identifier } =>
-                        Some(generate_getter_impl(&m, identifier.clone())),
+                        Some(generate_getter_impl(&m, identifier.clone(), self.is_module)),
                     MethodMetadata::StorageSetter{ visibility: _, identifier } =>
-                        Some(generate_setter_impl(&m, identifier.clone())),
+                        Some(generate_setter_impl(&m, identifier.clone(), self.is_module)),
                     MethodMetadata::StorageGetMut{ visibility: _, identifier } =>
-                        Some(generate_borrow_impl(&m, identifier.clone())),
-                    MethodMetadata::Module{ impl_path } =>
-                        Some(generate_module_getter_impl(&m, &impl_path)),
+                        Some(generate_borrow_impl(&m, identifier.clone(), self.is_module)),
+                    MethodMetadata::StorageGetOpt{ visibility: _, identifier } =>
+                        Some(generate_getter_opt_impl(m, identifier.clone(), self.is_module)),
+                    MethodMetadata::StorageSetOpt{ visibility: _, identifier } =>
+                        Some(generate_setter_opt_impl(m, identifier.clone(), self.is_module)),
+                    MethodMetadata::StorageIsEmpty{ visibility: _, identifier } =>
+                        Some(generate_is_empty_impl(m, identifier.clone(), self.is_module)),
+                    MethodMetadata::StorageClear{ visibility: _, identifier } =>
+                        Some(generate_clear_impl(m, identifier.clone(), self.is_module)),
+                    MethodMetadata::StorageUpdate{ identifier } =>
+                        Some(generate_update_impl(m, identifier.clone(), self.is_module)),
+                    MethodMetadata::Module{ impl_path, storage_prefix } =>
+                        Some(generate_module_getter_impl(m, impl_path, storage_prefix, self.is_module)),
                     _ => None
                 }
             })
@@ -158,11 +206,15 @@ impl Contract {
 
     pub fn generate_endpoints(&self) -> Vec<proc_macro2::TokenStream> {
         self.methods.iter()
+            // the fallback is only reached through the dispatcher
+            .filter(|m| !m.is_fallback)
             .filter_map(|m| {
                 if let Some(endpoint_name) = m.metadata.endpoint_name() {
                     let fn_ident = &m.name;
                     let call_method_ident = generate_call_method_name(&fn_ident);
+                    let label_cfg = generate_label_cfg(m, &self.methods);
                     let endpoint = quote! { 
+                        #label_cfg
                         #[no_mangle]
                         pub fn #endpoint_name ()
                         {
@@ -198,10 +250,71 @@ impl Contract {
                     }
                 })
                 .collect();
+        let unknown_function_arm = match self.methods.iter().find(|m| m.is_fallback) {
+            Some(fallback) => {
+                let call_method_ident = generate_call_method_name(&fallback.name);
+                quote! { _ => { self.#call_method_ident(); } }
+            },
+            None => quote! { _ => self.api.signal_error(err_msg::FUNCTION_NOT_FOUND) },
+        };
         quote! {      
             match fn_name {
                 #(#match_arms)*
-                other => panic!("No function named `{}` exists in contract.", other)
+                "callBack" => { self.callback(); },
+                #unknown_function_arm
+            }
+        }
+    }
+
+    pub fn generate_storage_keys_const(&self) -> proc_macro2::TokenStream {
+        generate_storage_keys_const(&self.contract_impl_name, &self.methods)
+    }
+
+    pub fn generate_module_storage_key_check(&self) -> Option<proc_macro2::TokenStream> {
+        generate_module_storage_key_check(&self.methods)
+    }
+
+    pub fn generate_proxy(&self) -> Option<proc_macro2::TokenStream> {
+        self.proxy_name.as_ref()
+            .map(|proxy_name| generate_proxy(proxy_name, &self.methods))
+    }
+
+    pub fn generate_wasm_exports_cfg(&self) -> proc_macro2::TokenStream {
+        generate_wasm_exports_cfg(&self.proxy_name)
+    }
+
+    /// The node does not route unknown functions to the fallback, so only the debugging mode supports it.
+    /// Building the contract for wasm fails, rather than deploying a contract that rejects what its tests accept.
+    pub fn generate_wasm_fallback_error(&self) -> Option<proc_macro2::TokenStream> {
+        let fallback = self.methods.iter().find(|m| m.is_fallback)?;
+        let wasm_exports_cfg = self.generate_wasm_exports_cfg();
+        let message = format!("The fallback `{}` is only supported in the debugging mode, the node does not call it for unknown functions or plain transfers.", fallback.name);
+        Some(quote! {
+            #wasm_exports_cfg
+            compile_error!(#message);
+        })
+    }
+
+    pub fn generate_test_client(&self) -> Option<proc_macro2::TokenStream> {
+        if self.is_module {
+            // module endpoints are called through the contract that includes them
+            return None;
+        }
+        Some(generate_test_client(&self.trait_name, &self.contract_impl_name, &self.methods))
+    }
+
+    pub fn generate_is_view_body(&self) -> proc_macro2::TokenStream {
+        let view_names: Vec<String> = self.methods.iter()
+            .filter(|m| m.metadata.is_view())
+            .map(|m| m.metadata.endpoint_name().unwrap().to_string())
+            .collect();
+        if view_names.is_empty() {
+            return quote! { false };
+        }
+        quote! {
+            match fn_name {
+                #(#view_names)|* => true,
+                _ => false,
             }
         }
     }
@@ -47,7 +47,7 @@ fn generate_callback_body_regular(methods: &[Method]) -> proc_macro2::TokenStrea
                                 .iter()
                                 .map(|arg| {
                                     if arg.is_callback_arg {
-                                        // callback args, loaded from storage via the tx hash
+                                        // callback args, loaded from the callback closure in storage
                                         match &arg.metadata {
                                             ArgMetadata::Single => {
                                                 generate_load_dyn_arg(arg,
@@ -105,7 +105,7 @@ fn generate_callback_body_regular(methods: &[Method]) -> proc_macro2::TokenStrea
         }
     } else {
         quote! {
-            let cb_data_raw = self.api.storage_load(&self.api.get_tx_hash().as_ref());
+            let cb_data_raw = numbat_wasm::async_call::load_callback_closure(&self.api);
             let mut cb_data_deserializer = numbat_wasm::call_data::CallDataDeserializer::new(cb_data_raw.as_slice());
             let mut ___arg_loader = DynEndpointArgLoader::new(&self.api);
             let ___err_handler = DynEndpointErrHandler::new(&self.api);
@@ -119,7 +119,7 @@ fn generate_callback_body_regular(methods: &[Method]) -> proc_macro2::TokenStrea
             numbat_wasm::check_no_more_args(&___arg_loader, &___err_handler);
 
             // cleanup
-            self.api.storage_store(&self.api.get_tx_hash().as_ref(), &[]); 
+            numbat_wasm::async_call::clear_callback_closure(&self.api);
         }
     }
 }
@@ -0,0 +1,186 @@
+use super::contract_gen_method::*;
+
+/// Storage identifier of an accessor, and whether arguments get appended to it to build keys.
+struct StorageKeyDecl<'a> {
+    method_name: String,
+    identifier: &'a str,
+    has_args: bool,
+}
+
+fn storage_key_decls(methods: &[Method]) -> Vec<StorageKeyDecl<'_>> {
+    methods.iter()
+        .filter_map(|m| {
+            let (identifier, nr_value_args) = match &m.metadata {
+                MethodMetadata::StorageGetter{ identifier, .. } |
+                MethodMetadata::StorageGetMut{ identifier, .. } |
+                MethodMetadata::StorageGetOpt{ identifier, .. } |
+                MethodMetadata::StorageIsEmpty{ identifier, .. } |
+                MethodMetadata::StorageClear{ identifier, .. } => (identifier, 0),
+                // the last argument is the value, or the update closure
+                MethodMetadata::StorageSetter{ identifier, .. } |
+                MethodMetadata::StorageSetOpt{ identifier, .. } |
+                MethodMetadata::StorageUpdate{ identifier } => (identifier, 1),
+                _ => return None,
+            };
+            Some(StorageKeyDecl {
+                method_name: m.name.to_string(),
+                identifier: identifier.as_str(),
+                has_args: m.method_args.len() > nr_value_args,
+            })
+        })
+        .collect()
+}
+
+fn is_proper_prefix(prefix: &str, s: &str) -> bool {
+    prefix.len() < s.len() && s.starts_with(prefix)
+}
+
+/// Rejects endpoints and views sharing the same name.
+/// Names shared with included modules get checked at compile time, see `generate_module_storage_key_check`.
+pub fn check_endpoint_names_unique(methods: &[Method]) {
+    for (i, m1) in methods.iter().enumerate() {
+        if let Some(name1) = m1.metadata.endpoint_name() {
+            for m2 in methods[i + 1..].iter() {
+                if m2.metadata.endpoint_name() == Some(name1) {
+                    panic!("Endpoint name `{}` is used by both `{}` and `{}`.", name1, m1.name, m2.name);
+                }
+            }
+        }
+    }
+}
+
+/// Keys are the storage identifier followed by the encoded arguments, if any.
+/// So if an identifier with arguments is a proper prefix of another identifier, their keys can overlap.
+pub fn check_storage_key_collisions(methods: &[Method]) {
+    let decls = storage_key_decls(methods);
+    for short in decls.iter().filter(|decl| decl.has_args) {
+        for long in decls.iter() {
+            if is_proper_prefix(short.identifier, long.identifier) {
+                panic!("Storage keys of `{}` and `{}` can collide, because \"{}\" is a prefix of \"{}\".",
+                    short.method_name, long.method_name, short.identifier, long.identifier);
+            }
+        }
+    }
+}
+
+fn module_decls(methods: &[Method]) -> Vec<(&syn::Ident, &proc_macro2::TokenTree, String)> {
+    methods.iter()
+        .filter_map(|m| match &m.metadata {
+            MethodMetadata::Module{ impl_path, storage_prefix } =>
+                Some((&m.name, impl_path, storage_prefix.clone().unwrap_or_default())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn endpoint_decls(methods: &[Method]) -> Vec<(&syn::Ident, String)> {
+    methods.iter()
+        .filter_map(|m| m.metadata.endpoint_name().map(|name| (&m.name, name.to_string())))
+        .collect()
+}
+
+/// The storage identifiers and endpoint names of the contract or module, for checking them against those of the contract that includes it.
+/// Nested modules are listed with their storage prefixes.
+/// The constant is named like the implementation struct, so importing the struct also brings it into scope,
+/// and it is not generic, so the checks using it get evaluated where they are defined.
+pub fn generate_storage_keys_const(contract_impl_ident: &syn::Path, methods: &[Method]) -> proc_macro2::TokenStream {
+    let mut keys: Vec<(&str, bool)> = storage_key_decls(methods).iter()
+        .map(|decl| (decl.identifier, decl.has_args))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    let key_tuples: Vec<proc_macro2::TokenStream> = keys.iter()
+        .map(|(identifier, has_args)| quote! { (#identifier, #has_args) })
+        .collect();
+    let mut endpoint_names: Vec<String> = endpoint_decls(methods).into_iter()
+        .map(|(_, name)| name)
+        .collect();
+    endpoint_names.sort_unstable();
+    let module_tuples: Vec<proc_macro2::TokenStream> = module_decls(methods).iter()
+        .map(|(_, module, prefix)| quote! { (#prefix, &#module) })
+        .collect();
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        pub const #contract_impl_ident: numbat_wasm::storage::StorageKeys = numbat_wasm::storage::StorageKeys {
+            own: &[#(#key_tuples),*],
+            endpoints: &[#(#endpoint_names),*],
+            modules: &[#(#module_tuples),*],
+        };
+    }
+}
+
+/// Module storage is only visible to the contract through the modules' storage keys constants,
+/// so the check is an anonymous constant, evaluated at compile time.
+/// Unlike within the contract, sharing a key with a module also counts as a collision.
+/// Endpoint names get checked the same way, since module endpoints are exported next to those of the contract.
+/// Modules with endpoints cannot have a storage prefix, since their exported endpoints would not use it.
+/// Returns nothing if there are no modules.
+pub fn generate_module_storage_key_check(methods: &[Method]) -> Option<proc_macro2::TokenStream> {
+    let modules = module_decls(methods);
+    if modules.is_empty() {
+        return None;
+    }
+
+    // a getter and a setter of the same value only need to be checked once
+    let mut decls: Vec<StorageKeyDecl<'_>> = Vec::new();
+    for decl in storage_key_decls(methods) {
+        if !decls.iter().any(|d| d.identifier == decl.identifier && d.has_args == decl.has_args) {
+            decls.push(decl);
+        }
+    }
+
+    let endpoints = endpoint_decls(methods);
+
+    let mut checks: Vec<proc_macro2::TokenStream> = Vec::new();
+    for (i, (getter, module, prefix)) in modules.iter().enumerate() {
+        if !prefix.is_empty() {
+            let message = format!("Module `{}` has endpoints, which are exported without its storage prefix. Modules with endpoints cannot have a storage prefix.", getter);
+            checks.push(quote! {
+                if numbat_wasm::storage::has_endpoints(&#module) {
+                    panic!(#message);
+                }
+            });
+        }
+        for (method_name, endpoint_name) in endpoints.iter() {
+            let message = format!("Endpoint name `{}` of `{}` is also used by module `{}`.",
+                endpoint_name, method_name, getter);
+            checks.push(quote! {
+                if numbat_wasm::storage::endpoint_name_used_by_any(#endpoint_name, &#module) {
+                    panic!(#message);
+                }
+            });
+        }
+        for decl in decls.iter() {
+            let identifier = decl.identifier;
+            let has_args = decl.has_args;
+            let message = format!("Storage key \"{}\" of `{}` can collide with a storage key of module `{}`.",
+                identifier, decl.method_name, getter);
+            checks.push(quote! {
+                if numbat_wasm::storage::storage_key_collides_with_any((#identifier, #has_args), #prefix, &#module) {
+                    panic!(#message);
+                }
+            });
+        }
+        for (other_getter, other_module, other_prefix) in modules[i + 1..].iter() {
+            let message = format!("Modules `{}` and `{}` have storage keys that can collide. Give them different storage prefixes.", getter, other_getter);
+            checks.push(quote! {
+                if numbat_wasm::storage::storage_key_sets_collide(#prefix, &#module, #other_prefix, &#other_module) {
+                    panic!(#message);
+                }
+            });
+            let message = format!("Modules `{}` and `{}` have endpoints with the same name.", getter, other_getter);
+            checks.push(quote! {
+                if numbat_wasm::storage::endpoint_name_sets_overlap(&#module, &#other_module) {
+                    panic!(#message);
+                }
+            });
+        }
+    }
+
+    Some(quote! {
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
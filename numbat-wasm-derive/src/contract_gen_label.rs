@@ -0,0 +1,145 @@
+use super::contract_gen_method::*;
+
+/// Label of endpoints that have none.
+static DEFAULT_LABEL: &str = "default";
+
+/// Cargo features select labels, by prefixing them with this.
+static LABEL_FEATURE_PREFIX: &str = "label-";
+
+fn endpoint_labels(m: &Method) -> Vec<String> {
+    if m.labels.is_empty() {
+        Vec::from([DEFAULT_LABEL.to_string()])
+    } else {
+        m.labels.clone()
+    }
+}
+
+fn label_features(labels: &[String]) -> Vec<String> {
+    labels.iter()
+        .map(|label| format!("{}{}", LABEL_FEATURE_PREFIX, label))
+        .collect()
+}
+
+/// Wasm outputs are selected with one `label-<name>` cargo feature per label,
+/// e.g. `label-default` for the main contract and `label-views` for a separate views contract.
+/// An endpoint is exported if any of its labels is selected, or if no label in the contract is,
+/// so contracts built without any label feature export all their endpoints.
+/// Contracts that use no labels at all get no condition, and neither does init, which every output needs,
+/// just like `callBack`.
+/// Only the exported wasm endpoints are filtered, the debug dispatcher always sees all of them.
+///
+/// The condition is evaluated in the crate that declares the endpoint.
+/// So the endpoints of a module in another crate follow the label features of the module crate,
+/// which the contract crate forwards, e.g. `label-views = ["my-module/label-views"]`.
+pub fn generate_label_cfg(m: &Method, methods: &[Method]) -> proc_macro2::TokenStream {
+    if m.is_init() || methods.iter().all(|m| m.labels.is_empty()) {
+        return quote! {};
+    }
+
+    let mut all_labels: Vec<String> = Vec::new();
+    for endpoint in methods.iter().filter(|m| m.metadata.endpoint_name().is_some() && !m.is_init()) {
+        for label in endpoint_labels(endpoint) {
+            if !all_labels.contains(&label) {
+                all_labels.push(label);
+            }
+        }
+    }
+
+    let own_features = label_features(&endpoint_labels(m));
+    let all_features = label_features(&all_labels);
+    quote! {
+        #[cfg(any(
+            #(feature = #own_features,)*
+            not(any(#(feature = #all_features),*))
+        ))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_methods(contract_trait: syn::ItemTrait) -> Vec<Method> {
+        contract_trait.items.iter()
+            .map(|item| match item {
+                syn::TraitItem::Method(m) => Method::parse(m),
+                _ => panic!("Only methods expected"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_label_cfg() {
+        let methods = parse_methods(parse_quote! {
+            pub trait Labeled {
+                #[init]
+                fn init(&self) {}
+
+                #[endpoint]
+                fn deposit(&self) {}
+
+                #[view(getPrice)]
+                #[label("views")]
+                fn get_price(&self) -> u32 { 0 }
+
+                #[endpoint(setPrice)]
+                #[label("views")]
+                #[label("admin")]
+                fn set_price(&self, price: u32) {}
+
+                fn helper(&self) {}
+            }
+        });
+        let cfg_strings: Vec<String> = methods.iter()
+            .map(|m| generate_label_cfg(m, &methods).to_string())
+            .collect();
+        assert_eq!(cfg_strings[0], quote! {}.to_string());
+        assert_eq!(cfg_strings[1], quote! {
+            #[cfg(any(
+                feature = "label-default",
+                not(any(feature = "label-default", feature = "label-views", feature = "label-admin"))
+            ))]
+        }.to_string());
+        assert_eq!(cfg_strings[2], quote! {
+            #[cfg(any(
+                feature = "label-views",
+                not(any(feature = "label-default", feature = "label-views", feature = "label-admin"))
+            ))]
+        }.to_string());
+        assert_eq!(cfg_strings[3], quote! {
+            #[cfg(any(
+                feature = "label-views",
+                feature = "label-admin",
+                not(any(feature = "label-default", feature = "label-views", feature = "label-admin"))
+            ))]
+        }.to_string());
+    }
+
+    #[test]
+    fn test_no_labels_no_cfg() {
+        let methods = parse_methods(parse_quote! {
+            pub trait Unlabeled {
+                #[init]
+                fn init(&self) {}
+
+                #[endpoint]
+                fn deposit(&self) {}
+            }
+        });
+        for m in methods.iter() {
+            assert!(generate_label_cfg(m, &methods).is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Init cannot have labels, it is exported in every label.")]
+    fn test_init_label_rejected() {
+        parse_methods(parse_quote! {
+            pub trait LabeledInit {
+                #[init]
+                #[label("views")]
+                fn init(&self) {}
+            }
+        });
+    }
+}
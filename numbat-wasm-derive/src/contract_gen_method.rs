@@ -13,10 +13,19 @@ pub enum Visibility {
     /// Means it gets a smart contract function generated for it
     Endpoint(syn::Ident),
 
+    /// Endpoint that only reads state. Its body gets an API that rejects state changes.
+    View(syn::Ident),
+
     /// Can be used only inside the smart contract, even if it is public in the module.
     Private
 }
 
+impl Visibility {
+    pub fn is_public(&self) -> bool {
+        !matches!(self, Visibility::Private)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum MethodMetadata {
     Regular{ visibility: Visibility, payable: bool },
@@ -26,7 +35,12 @@ pub enum MethodMetadata {
     StorageGetter{ visibility: Visibility, identifier: String },
     StorageSetter{ visibility: Visibility, identifier: String },
     StorageGetMut{ visibility: Visibility, identifier: String },
-    Module{ impl_path: proc_macro2::TokenTree },
+    StorageGetOpt{ visibility: Visibility, identifier: String },
+    StorageSetOpt{ visibility: Visibility, identifier: String },
+    StorageIsEmpty{ visibility: Visibility, identifier: String },
+    StorageClear{ visibility: Visibility, identifier: String },
+    StorageUpdate{ identifier: String },
+    Module{ impl_path: proc_macro2::TokenTree, storage_prefix: Option<String> },
 }
 
 impl MethodMetadata {
@@ -35,11 +49,27 @@ impl MethodMetadata {
             MethodMetadata::Regular{ visibility: Visibility::Endpoint(e), ..} |
             MethodMetadata::StorageGetter{ visibility: Visibility::Endpoint(e), ..} |
             MethodMetadata::StorageSetter{ visibility: Visibility::Endpoint(e), ..} |
-            MethodMetadata::StorageGetMut{ visibility: Visibility::Endpoint(e), ..} => Some(e),
+            MethodMetadata::StorageGetMut{ visibility: Visibility::Endpoint(e), ..} |
+            MethodMetadata::StorageGetOpt{ visibility: Visibility::Endpoint(e), ..} |
+            MethodMetadata::StorageSetOpt{ visibility: Visibility::Endpoint(e), ..} |
+            MethodMetadata::StorageIsEmpty{ visibility: Visibility::Endpoint(e), ..} |
+            MethodMetadata::StorageClear{ visibility: Visibility::Endpoint(e), ..} => Some(e),
+            MethodMetadata::Regular{ visibility: Visibility::View(e), ..} |
+            MethodMetadata::StorageGetter{ visibility: Visibility::View(e), ..} |
+            MethodMetadata::StorageGetOpt{ visibility: Visibility::View(e), ..} |
+            MethodMetadata::StorageIsEmpty{ visibility: Visibility::View(e), ..} => Some(e),
             _ => None,
         }
     }
 
+    pub fn is_view(&self) -> bool {
+        matches!(self,
+            MethodMetadata::Regular{ visibility: Visibility::View(_), ..} |
+            MethodMetadata::StorageGetter{ visibility: Visibility::View(_), ..} |
+            MethodMetadata::StorageGetOpt{ visibility: Visibility::View(_), ..} |
+            MethodMetadata::StorageIsEmpty{ visibility: Visibility::View(_), ..})
+    }
+
     pub fn has_implementation(&self) -> bool {
         match self {
             MethodMetadata::Regular{..} | 
@@ -58,6 +88,12 @@ pub struct Method {
     pub method_args: Vec<MethodArg>,
     pub return_type: syn::ReturnType,
     pub body: Option<syn::Block>,
+    /// Labels of the wasm outputs that export the endpoint. Empty means the default output only.
+    pub labels: Vec<String>,
+    /// The fallback receives calls to unknown functions and plain value transfers, through the dispatcher.
+    /// It is not exported to wasm, where it could only be called by its own name,
+    /// so for now only the debugger mock routes calls to it.
+    pub is_fallback: bool,
 }
 
 const INIT_ENDPOINT_NAME: &str = "init";
@@ -65,6 +101,24 @@ const INIT_ENDPOINT_NAME: &str = "init";
 fn process_visibility(m: &syn::TraitItemMethod) -> Visibility {
     let endpoint_attr_opt = EndpointAttribute::parse(m);
     let view_attr_opt = ViewAttribute::parse(m);
+
+    // fallback
+    if is_fallback(m) {
+        if is_init(m) {
+            panic!("Cannot annotate with both #[init] and #[fallback].");
+        }
+        if endpoint_attr_opt.is_some() {
+            panic!("Cannot annotate with both #[endpoint] and #[fallback].");
+        }
+        if view_attr_opt.is_some() {
+            panic!("Cannot annotate with both #[view] and #[fallback].");
+        }
+        let fallback_name_str = &m.sig.ident.to_string();
+        if reserved::is_reserved(fallback_name_str) {
+            panic!("Cannot declare fallback with name '{}', because that name is reserved by the Andes API.", fallback_name_str);
+        }
+        return Visibility::Endpoint(m.sig.ident.clone());
+    }
     
     // init
     let init = is_init(m);
@@ -110,7 +164,7 @@ fn process_visibility(m: &syn::TraitItemMethod) -> Visibility {
         if reserved::is_reserved(view_name_str) {
             panic!("Cannot declare view with name '{}', because that name is reserved by the Andes API.", view_name_str);
         }
-        return Visibility::Endpoint(view_ident);
+        return Visibility::View(view_ident);
     }
 
     Visibility::Private
@@ -126,13 +180,27 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
     let storage_get_opt = StorageGetAttribute::parse(m);
     let storage_set_opt = StorageSetAttribute::parse(m);
     let storage_get_mut_opt = StorageGetMutAttribute::parse(m);
+    let storage_get_optional_opt = StorageGetOptAttribute::parse(m);
+    let storage_set_optional_opt = StorageSetOptAttribute::parse(m);
+    let storage_is_empty_opt = StorageIsEmptyAttribute::parse(m);
+    let storage_clear_opt = StorageClearAttribute::parse(m);
+    let storage_update_opt = StorageUpdateAttribute::parse(m);
+    let other_storage_accessor = storage_get_optional_opt.is_some() ||
+        storage_set_optional_opt.is_some() ||
+        storage_is_empty_opt.is_some() ||
+        storage_clear_opt.is_some() ||
+        storage_update_opt.is_some();
     let module_opt = ModuleAttribute::parse(m);
+    let storage_prefix_opt = StoragePrefixAttribute::parse(m);
+    if storage_prefix_opt.is_some() && module_opt.is_none() {
+        panic!("Only modules can have a storage prefix.");
+    }
 
     if let Some(event_attr) = event_opt {
         if payable {
             panic!("Events cannot be payable.");
         }
-        if let Visibility::Endpoint(_) = visibility {
+        if visibility.is_public() {
             panic!("Events cannot be endpoints.");
         }
         if callback || callback_raw {
@@ -147,6 +215,9 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
         if storage_get_mut_opt.is_some() {
             panic!("Events cannot be storage borrow getters.");
         }
+        if other_storage_accessor {
+            panic!("Events cannot be storage accessors.");
+        }
         if module_opt.is_some() {
             panic!("Events cannot be modules.");
         }
@@ -158,7 +229,7 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
         if payable {
             panic!("Callback methods cannot be marked payable.");
         }
-        if let Visibility::Endpoint(_) = visibility {
+        if visibility.is_public() {
             panic!("Callbacks cannot be endpoints.");
         }
         if storage_get_opt.is_some() {
@@ -170,6 +241,9 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
         if storage_get_mut_opt.is_some() {
             panic!("Callbacks cannot be storage borrow getters.");
         }
+        if other_storage_accessor {
+            panic!("Callbacks cannot be storage accessors.");
+        }
         if module_opt.is_some() {
             panic!("Callbacks cannot be modules.");
         }
@@ -208,6 +282,9 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
         if module_opt.is_some() {
             panic!("Storage setters cannot be modules.");
         }
+        if let Visibility::View(_) = visibility {
+            panic!("Storage setters cannot be views.");
+        }
         MethodMetadata::StorageSetter{
             visibility,
             identifier: storage_set.identifier,
@@ -222,21 +299,108 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
         if module_opt.is_some() {
             panic!("Storage mutable getters cannot be modules.");
         }
+        if let Visibility::View(_) = visibility {
+            panic!("Storage mutable getters cannot be views.");
+        }
         MethodMetadata::StorageGetMut{
             visibility,
             identifier: storage_get_mut.identifier,
         }
+    } else if let Some(storage_get_optional) = storage_get_optional_opt {
+        if payable {
+            panic!("Optional storage getters cannot be marked payable.");
+        }
+        if m.default.is_some() {
+            panic!("Optional storage getters cannot have an implementations provided in the trait.");
+        }
+        if module_opt.is_some() {
+            panic!("Optional storage getters cannot be modules.");
+        }
+        MethodMetadata::StorageGetOpt{
+            visibility,
+            identifier: storage_get_optional.identifier,
+        }
+    } else if let Some(storage_set_optional) = storage_set_optional_opt {
+        if payable {
+            panic!("Optional storage setters cannot be marked payable.");
+        }
+        if m.default.is_some() {
+            panic!("Optional storage setters cannot have an implementations provided in the trait.");
+        }
+        if module_opt.is_some() {
+            panic!("Optional storage setters cannot be modules.");
+        }
+        if let Visibility::View(_) = visibility {
+            panic!("Optional storage setters cannot be views.");
+        }
+        MethodMetadata::StorageSetOpt{
+            visibility,
+            identifier: storage_set_optional.identifier,
+        }
+    } else if let Some(storage_is_empty) = storage_is_empty_opt {
+        if payable {
+            panic!("Storage emptiness checks cannot be marked payable.");
+        }
+        if m.default.is_some() {
+            panic!("Storage emptiness checks cannot have an implementations provided in the trait.");
+        }
+        if module_opt.is_some() {
+            panic!("Storage emptiness checks cannot be modules.");
+        }
+        MethodMetadata::StorageIsEmpty{
+            visibility,
+            identifier: storage_is_empty.identifier,
+        }
+    } else if let Some(storage_clear) = storage_clear_opt {
+        if payable {
+            panic!("Storage clear methods cannot be marked payable.");
+        }
+        if m.default.is_some() {
+            panic!("Storage clear methods cannot have an implementations provided in the trait.");
+        }
+        if module_opt.is_some() {
+            panic!("Storage clear methods cannot be modules.");
+        }
+        if let Visibility::View(_) = visibility {
+            panic!("Storage clear methods cannot be views.");
+        }
+        MethodMetadata::StorageClear{
+            visibility,
+            identifier: storage_clear.identifier,
+        }
+    } else if let Some(storage_update) = storage_update_opt {
+        if payable {
+            panic!("Storage update methods cannot be marked payable.");
+        }
+        if m.default.is_some() {
+            panic!("Storage update methods cannot have an implementations provided in the trait.");
+        }
+        if module_opt.is_some() {
+            panic!("Storage update methods cannot be modules.");
+        }
+        if visibility.is_public() {
+            panic!("Storage update methods cannot be endpoints, since the update closure cannot be passed in a call.");
+        }
+        MethodMetadata::StorageUpdate{
+            identifier: storage_update.identifier,
+        }
     } else if let Some(module_attr) = module_opt {
         if m.default.is_some() {
             panic!("Module declarations cannot have an implementations provided in the trait.");
         }
         MethodMetadata::Module{
             impl_path: module_attr.arg,
+            storage_prefix: storage_prefix_opt.map(|attr| attr.prefix),
         }
     } else {
         if m.default.is_none() {
             panic!("Regular methods need an implementation.");
         }
+        if payable {
+            if let Visibility::View(_) = visibility {
+                panic!("Views cannot be payable.");
+            }
+        }
         MethodMetadata::Regular{
             visibility,
             payable,
@@ -244,11 +408,64 @@ fn extract_metadata(m: &syn::TraitItemMethod) -> MethodMetadata {
     }
 }
 
+/// Unknown calls come with arbitrary arguments, so the fallback can only take the payment.
+/// Checked before the arguments get extracted, for clearer messages.
+fn check_fallback(m: &syn::TraitItemMethod, metadata: &MethodMetadata) {
+    if !matches!(metadata, MethodMetadata::Regular{ .. }) {
+        panic!("Only regular methods can be the fallback.");
+    }
+    for arg in m.sig.inputs.iter() {
+        if let syn::FnArg::Typed(pat_typed) = arg {
+            if !is_payment(pat_typed) {
+                panic!("The fallback cannot have arguments, other than the #[payment].");
+            }
+            if !is_payable(m) {
+                panic!("The fallback must be #[payable] to take the #[payment].");
+            }
+        }
+    }
+}
+
+fn extract_labels(m: &syn::TraitItemMethod, metadata: &MethodMetadata) -> Vec<String> {
+    let labels: Vec<String> = LabelAttribute::parse_all(m)
+        .into_iter()
+        .map(|label_attr| label_attr.label)
+        .collect();
+    if labels.is_empty() {
+        return labels;
+    }
+    if metadata.endpoint_name().is_none() {
+        panic!("Only endpoints and views can have labels.");
+    }
+    if is_init(m) {
+        panic!("Init cannot have labels, it is exported in every label.");
+    }
+    for (i, label) in labels.iter().enumerate() {
+        // labels are selected via cargo features, so they must be valid feature names
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            panic!("Invalid label '{}': only letters, digits, '-' and '_' allowed.", label);
+        }
+        if labels[..i].contains(label) {
+            panic!("Duplicate label '{}'.", label);
+        }
+    }
+    labels
+}
+
 impl Method {
+    pub fn is_init(&self) -> bool {
+        matches!(self.metadata.endpoint_name(), Some(endpoint_name) if endpoint_name == INIT_ENDPOINT_NAME)
+    }
+
     pub fn parse(m: &syn::TraitItemMethod) -> Method {
         let metadata = extract_metadata(m);
+        let is_fallback = is_fallback(m);
+        if is_fallback {
+            check_fallback(m, &metadata);
+        }
         let allow_callback_args = if let MethodMetadata::Callback = metadata { true } else { false };
         let method_args = extract_method_args(m, is_payable(m), allow_callback_args);
+        let labels = extract_labels(m, &metadata);
         Method {
             metadata,
             name: m.sig.ident.clone(),
@@ -256,6 +473,8 @@ impl Method {
             method_args,
             return_type: m.sig.output.clone(),
             body: m.default.clone(),
+            labels,
+            is_fallback,
         }
     }
 }
@@ -286,13 +505,26 @@ impl Method {
     }
 
     pub fn generate_call_to_method(&self) -> proc_macro2::TokenStream {
+        self.generate_call_to_method_on(&quote! { self })
+    }
+
+    pub fn generate_call_to_method_on(&self, receiver: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
         let fn_ident = &self.name;
         let arg_values: Vec<proc_macro2::TokenStream> = self.method_args
             .iter()
             .map(|arg| generate_arg_call_name(arg))
             .collect();
         quote! {
-            self.#fn_ident (#(#arg_values),*)
+            #receiver.#fn_ident (#(#arg_values),*)
+        }
+    }
+
+    /// Views are called on a copy of the contract whose API rejects state changes.
+    fn generate_endpoint_call(&self) -> proc_macro2::TokenStream {
+        if self.metadata.is_view() {
+            self.generate_call_to_method_on(&quote! { self.___read_only() })
+        } else {
+            self.generate_call_to_method()
         }
     }
 
@@ -330,6 +562,9 @@ impl Method {
                     match &arg.metadata {
                         ArgMetadata::Single => {
                             arg_index += 1;
+                            if is_borrowed_arg(arg) {
+                                return generate_load_single_arg_borrowed(arg, &quote!{ #arg_index });
+                            }
                             let pat = &arg.pat;
                             let arg_get = generate_load_single_arg(arg, &quote!{ #arg_index });
                             quote! {
@@ -347,17 +582,25 @@ impl Method {
                 .collect();
 
         let call_method_ident = generate_call_method_name(&self.name);
-        let call = self.generate_call_to_method();
+        let call = self.generate_endpoint_call();
         let body_with_result = generate_body_with_result(&self.return_type, &call);
         let nr_args = arg_index + 1;
+        // the fallback ignores the arguments of the unknown call
+        let check_nr_args_snippet = if self.is_fallback {
+            quote! {}
+        } else {
+            quote! {
+                if !self.api.check_num_arguments(#nr_args) {
+                    return;
+                }
+            }
+        };
 
         quote! {
             #[inline]
             fn #call_method_ident (&self) {
                 #payable_snippet
-                if !self.api.check_num_arguments(#nr_args) {
-                    return;
-                }
+                #check_nr_args_snippet
                 #(#arg_init_snippets)*
                 #body_with_result
             }
@@ -395,7 +638,7 @@ impl Method {
                 .collect();
 
         let call_method_ident = generate_call_method_name(&self.name);
-        let call = self.generate_call_to_method();
+        let call = self.generate_endpoint_call();
         let body_with_result = generate_body_with_result(&self.return_type, &call);
 
         quote! {
@@ -415,4 +658,38 @@ impl Method {
         }
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payable_fallback() {
+        let m = Method::parse(&parse_quote! {
+            #[fallback]
+            #[payable]
+            fn receive(&self, #[payment] payment: BigUint) {}
+        });
+        assert!(m.is_fallback);
+        assert!(matches!(m.metadata, MethodMetadata::Regular{ payable: true, .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "The fallback must be #[payable] to take the #[payment].")]
+    fn test_fallback_payment_not_payable() {
+        Method::parse(&parse_quote! {
+            #[fallback]
+            fn receive(&self, #[payment] payment: BigUint) {}
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "The fallback cannot have arguments, other than the #[payment].")]
+    fn test_fallback_with_arguments() {
+        Method::parse(&parse_quote! {
+            #[fallback]
+            #[payable]
+            fn receive(&self, #[payment] payment: BigUint, tag: u32) {}
+        });
+    }
+}
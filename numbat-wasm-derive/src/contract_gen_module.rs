@@ -1,15 +1,36 @@
 use super::contract_gen_method::*;
+use super::util::*;
 
-pub fn generate_module_getter_impl(m: &Method, impl_path: &proc_macro2::TokenTree) -> proc_macro2::TokenStream {
+/// A module inside another module gets the storage prefix of its parent, followed by its own.
+pub fn generate_module_getter_impl(m: &Method, impl_path: &proc_macro2::TokenTree, storage_prefix: &Option<String>, in_module: bool) -> proc_macro2::TokenStream {
     let msig = m.generate_sig();
     if !m.method_args.is_empty() {
         panic!("module getter cannot have arguments");
     }
     // TODO: check return type
 
+    let prefix_literal = storage_prefix.as_ref()
+        .map(|prefix| array_literal(prefix.as_bytes()));
+    let body = match (in_module, prefix_literal) {
+        (false, None) => quote! {
+            #impl_path::new(self.api.clone())
+        },
+        (false, Some(prefix_literal)) => quote! {
+            #impl_path::with_storage_prefix(self.api.clone(), #prefix_literal.to_vec())
+        },
+        (true, None) => quote! {
+            #impl_path::with_storage_prefix(self.api.clone(), self.storage_prefix.clone())
+        },
+        (true, Some(prefix_literal)) => quote! {
+            let mut storage_prefix = self.storage_prefix.clone();
+            storage_prefix.extend_from_slice(&#prefix_literal[..]);
+            #impl_path::with_storage_prefix(self.api.clone(), storage_prefix)
+        },
+    };
+
     quote! {
         #msig {
-            #impl_path::new(self.api.clone())
+            #body
         }
     }
 }
@@ -18,6 +18,10 @@ pub fn generate_payable_snippet(m: &Method) -> proc_macro2::TokenStream {
         MethodMetadata::StorageGetter{ .. } => not_payable_snippet,
         MethodMetadata::StorageSetter{ .. } => not_payable_snippet,
         MethodMetadata::StorageGetMut{ .. } => not_payable_snippet,
+        MethodMetadata::StorageGetOpt{ .. } => not_payable_snippet,
+        MethodMetadata::StorageSetOpt{ .. } => not_payable_snippet,
+        MethodMetadata::StorageIsEmpty{ .. } => not_payable_snippet,
+        MethodMetadata::StorageClear{ .. } => not_payable_snippet,
         _ => quote!{},
     }
 }
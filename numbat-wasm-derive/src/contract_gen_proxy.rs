@@ -0,0 +1,76 @@
+use super::contract_gen_method::*;
+use super::arg_str_serialize::*;
+use super::snippets;
+use super::util::*;
+
+/// Contracts with a proxy get imported by the contracts that call them,
+/// so their wasm exports are behind this cargo feature, which the contract crate enables by default
+/// and dependents turn off with `default-features = false`.
+/// Otherwise the callee's `init`, `callBack` and endpoints would clash with those of the caller.
+static WASM_ENDPOINTS_FEATURE: &str = "wasm-endpoints";
+
+/// Condition for the wasm exports of the contract: endpoints, `callBack` and what they need.
+pub fn generate_wasm_exports_cfg(proxy_name: &Option<syn::Ident>) -> proc_macro2::TokenStream {
+    if proxy_name.is_some() {
+        quote! { #[cfg(all(target_arch = "wasm32", feature = #WASM_ENDPOINTS_FEATURE))] }
+    } else {
+        quote! { #[cfg(target_arch = "wasm32")] }
+    }
+}
+
+fn generate_proxy_sig(m: &Method) -> proc_macro2::TokenStream {
+    let method_name = &m.name;
+    let arg_decl = arg_declarations(&m.method_args);
+    quote! {
+        fn #method_name ( &self , #(#arg_decl),* ) -> numbat_wasm::AsyncCall<BigUint>
+    }
+}
+
+fn generate_proxy_method_impl(m: &Method, endpoint_name: &syn::Ident) -> proc_macro2::TokenStream {
+    let msig = generate_proxy_sig(m);
+
+    let CallArgs { push_snippets: arg_push_snippets, payment } = call_args(&m.method_args, call_data_push);
+    let payment_snippet = payment.map(|payment| quote! { .with_value(#payment) });
+
+    let endpoint_name_literal = array_literal(endpoint_name.to_string().as_bytes());
+    quote! {
+        #msig {
+            let mut call_data_ser = numbat_wasm::call_data::CallDataSerializer::new( & #endpoint_name_literal );
+            #(#arg_push_snippets)*
+            numbat_wasm::AsyncCall::from_call_data(&self.address, call_data_ser)
+                #payment_snippet
+        }
+    }
+}
+
+/// Proxy to other instances of the contract, implemented for `OtherContractHandle`.
+/// Each endpoint gets a method that prepares the async call, including the payment,
+/// and returns it so the caller can add a value, gas limit or callback before executing it.
+pub fn generate_proxy(proxy_name: &syn::Ident, methods: &[Method]) -> proc_macro2::TokenStream {
+    let endpoints: Vec<(&Method, &syn::Ident)> = methods.iter()
+        .filter_map(|m| m.metadata.endpoint_name().map(|endpoint_name| (m, endpoint_name)))
+        .filter(|(m, endpoint_name)| *endpoint_name != "init" && !m.is_fallback)
+        .collect();
+    let sigs: Vec<proc_macro2::TokenStream> = endpoints.iter()
+        .map(|(m, _)| generate_proxy_sig(m))
+        .collect();
+    let impls: Vec<proc_macro2::TokenStream> = endpoints.iter()
+        .map(|(m, endpoint_name)| generate_proxy_method_impl(m, endpoint_name))
+        .collect();
+
+    let bi_where = snippets::big_int_where();
+    let api_where = snippets::api_where();
+    quote! {
+        pub trait #proxy_name<BigInt, BigUint>
+        #bi_where
+        {
+            #(#sigs ;)*
+        }
+
+        impl<T, BigInt, BigUint> #proxy_name<BigInt, BigUint> for OtherContractHandle<T, BigInt, BigUint>
+        #api_where
+        {
+            #(#impls)*
+        }
+    }
+}
@@ -2,10 +2,10 @@ use super::contract_gen_method::*;
 use super::arg_def::*;
 use super::util::*;
 
-fn storage_store_snippet(arg: &MethodArg) -> proc_macro2::TokenStream {
+fn storage_store_snippet(arg: &MethodArg, store_fn: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
     let pat = &arg.pat;
     quote! {
-        numbat_wasm::storage_set(&self.api, &key[..], & #pat);
+        numbat_wasm::#store_fn(&self.api, &key[..], & #pat);
     }
 }
 
@@ -15,23 +15,30 @@ fn storage_load_snippet(_ty: &syn::Type) -> proc_macro2::TokenStream {
     }
 }
 
-fn generate_key_snippet(key_args: &[MethodArg], identifier: String) -> proc_macro2::TokenStream {
+/// Modules have their instance storage prefix added in front of every key.
+fn generate_key_snippet(key_args: &[MethodArg], identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
     let id_literal = array_literal(identifier.as_bytes());
-    if key_args.is_empty() {
+    let key_appends: Vec<proc_macro2::TokenStream> = key_args.iter().map(|arg| {
+        let arg_pat = &arg.pat;
+        quote! {
+            if let Result::Err(encode_error) = #arg_pat.dep_encode_to(&mut key) {
+                self.api.signal_error(encode_error.message_bytes());
+            }
+        }
+    }).collect();
+    if prefixed {
+        quote! {
+            let mut key: Vec<u8> = self.storage_prefix.clone();
+            key.extend_from_slice(&#id_literal[..]);
+            #(#key_appends)*
+        }
+    } else if key_args.is_empty() {
         // hardcode key
         quote! {
             let key: &'static [u8] = &#id_literal;
         }
     } else {
         // build key from arguments
-        let key_appends: Vec<proc_macro2::TokenStream> = key_args.iter().map(|arg| {
-            let arg_pat = &arg.pat;
-            quote! {
-                if let Result::Err(encode_error) = #arg_pat.dep_encode_to(&mut key) {
-                    self.api.signal_error(encode_error.message_bytes());
-                }
-            }
-        }).collect();
         quote! {
             let mut key: Vec<u8> = #id_literal.to_vec();
             #(#key_appends)*
@@ -39,9 +46,9 @@ fn generate_key_snippet(key_args: &[MethodArg], identifier: String) -> proc_macr
     }
 }
 
-pub fn generate_getter_impl(m: &Method, identifier: String) -> proc_macro2::TokenStream {
+pub fn generate_getter_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
     let msig = m.generate_sig();
-    let key_snippet = generate_key_snippet(&m.method_args.as_slice(), identifier);
+    let key_snippet = generate_key_snippet(&m.method_args.as_slice(), identifier, prefixed);
     match m.return_type.clone() {
         syn::ReturnType::Default => panic!("getter should return some value"),
         syn::ReturnType::Type(_, ty) => {
@@ -56,7 +63,16 @@ pub fn generate_getter_impl(m: &Method, identifier: String) -> proc_macro2::Toke
     }
 }
 
-pub fn generate_setter_impl(m: &Method, identifier: String) -> proc_macro2::TokenStream {
+pub fn generate_setter_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    generate_store_impl(m, identifier, prefixed, quote! { storage_set })
+}
+
+/// Stores the value so that it reads back as `Some` from an optional getter, even if it encodes as no bytes.
+pub fn generate_setter_opt_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    generate_store_impl(m, identifier, prefixed, quote! { storage_set_opt })
+}
+
+fn generate_store_impl(m: &Method, identifier: String, prefixed: bool, store_fn: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
     let msig = m.generate_sig();
     if m.method_args.is_empty() {
         panic!("setter must have at least one argument, for the value");
@@ -65,9 +81,9 @@ pub fn generate_setter_impl(m: &Method, identifier: String) -> proc_macro2::Toke
         panic!("setter should not return anything");
     }
     let key_args = &m.method_args[..m.method_args.len()-1];
-    let key_snippet = generate_key_snippet(key_args, identifier);
+    let key_snippet = generate_key_snippet(key_args, identifier, prefixed);
     let value_arg = &m.method_args[m.method_args.len()-1];
-    let store_snippet = storage_store_snippet(value_arg);
+    let store_snippet = storage_store_snippet(value_arg, store_fn);
     quote! {
         #msig {
             #key_snippet
@@ -76,10 +92,80 @@ pub fn generate_setter_impl(m: &Method, identifier: String) -> proc_macro2::Toke
     }
 }
 
-pub fn generate_borrow_impl(m: &Method, identifier: String) -> proc_macro2::TokenStream {
+pub fn generate_getter_opt_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    let msig = m.generate_sig();
+    let key_snippet = generate_key_snippet(m.method_args.as_slice(), identifier, prefixed);
+    if m.return_type == syn::ReturnType::Default {
+        panic!("optional getter should return an Option");
+    }
+    quote! {
+        #msig {
+            #key_snippet
+            numbat_wasm::storage_get_opt(&self.api, &key[..])
+        }
+    }
+}
+
+pub fn generate_is_empty_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    let msig = m.generate_sig();
+    let key_snippet = generate_key_snippet(m.method_args.as_slice(), identifier, prefixed);
+    quote! {
+        #msig {
+            #key_snippet
+            numbat_wasm::storage_is_empty(&self.api, &key[..])
+        }
+    }
+}
+
+pub fn generate_clear_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    let msig = m.generate_sig();
+    if m.return_type != syn::ReturnType::Default {
+        panic!("storage clear should not return anything");
+    }
+    let key_snippet = generate_key_snippet(m.method_args.as_slice(), identifier, prefixed);
+    quote! {
+        #msig {
+            #key_snippet
+            numbat_wasm::storage_clear(&self.api, &key[..]);
+        }
+    }
+}
+
+/// The last argument is the closure that modifies the value, the ones before it make up the key.
+/// Whatever the closure returns is returned by the method.
+/// The closure argument must be a plain identifier, since it gets called by name.
+pub fn generate_update_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
     let msig = m.generate_sig();
-    let key_snippet = generate_key_snippet(&m.method_args.as_slice(), identifier);
     if m.method_args.is_empty() {
+        panic!("storage update must have at least one argument, for the update closure");
+    }
+    let key_args = &m.method_args[..m.method_args.len()-1];
+    let key_snippet = generate_key_snippet(key_args, identifier, prefixed);
+    let closure_ident = match &m.method_args[m.method_args.len()-1].pat {
+        syn::Pat::Ident(pat_ident) if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() => &pat_ident.ident,
+        _ => panic!("storage update closure argument must be a plain identifier"),
+    };
+    // the key snippet declares `key`, so it gets its own block to not shadow the closure
+    quote! {
+        #msig {
+            let ___key___ = {
+                #key_snippet
+                key
+            };
+            #[allow(unused_mut)]
+            let mut ___update___ = #closure_ident;
+            let mut ___value___ = numbat_wasm::storage_get(&self.api, &___key___[..]);
+            let ___result___ = ___update___(&mut ___value___);
+            numbat_wasm::storage_set(&self.api, &___key___[..], &___value___);
+            ___result___
+        }
+    }
+}
+
+pub fn generate_borrow_impl(m: &Method, identifier: String, prefixed: bool) -> proc_macro2::TokenStream {
+    let msig = m.generate_sig();
+    let key_snippet = generate_key_snippet(&m.method_args.as_slice(), identifier, prefixed);
+    if m.method_args.is_empty() && !prefixed {
         // const key
         quote! {
             #msig {
@@ -0,0 +1,166 @@
+use super::contract_gen_method::*;
+use super::arg_def::*;
+use super::arg_str_serialize::*;
+use super::snippets;
+
+fn arg_push_snippet(
+        arg: &MethodArg,
+        push_call: proc_macro2::TokenStream,
+        extra_push_args: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+
+    let pat = &arg.pat;
+    let arg_ref = match &arg.ty {
+        syn::Type::Path(_) => quote! { &#pat },
+        syn::Type::Reference(type_reference) => {
+            if type_reference.mutability.is_some() {
+                panic!("Mutable references not supported as contract method arguments");
+            }
+            if is_borrowed_arg(arg) {
+                quote! { &#pat }
+            } else {
+                quote! { #pat }
+            }
+        },
+        other_arg => panic!("Unsupported argument type: {:?}, neither path nor reference", other_arg)
+    };
+    quote! {
+        if let Result::Err(sc_err) = #push_call(#arg_ref, &mut call_data_ser #extra_push_args) {
+            return Result::Err(sc_err.as_bytes().to_vec());
+        }
+    }
+}
+
+/// What the client needs to make a call: its own argument declarations,
+/// the code serializing the endpoint arguments and the call value.
+struct ClientCallArgs {
+    arg_decl: Vec<proc_macro2::TokenStream>,
+    arg_push_snippets: Vec<proc_macro2::TokenStream>,
+    call_value_expr: proc_macro2::TokenStream,
+}
+
+fn client_call_args(m: &Method, payable: bool) -> ClientCallArgs {
+    let mut arg_decl = arg_declarations(&m.method_args);
+
+    let CallArgs { push_snippets: arg_push_snippets, payment } = call_args(&m.method_args, |arg, expected_count_expr| {
+        match expected_count_expr {
+            Some(count_expr) => arg_push_snippet(arg,
+                quote! { numbat_wasm::AsynCallArg::push_async_arg_exact },
+                quote! { , #count_expr }),
+            None => arg_push_snippet(arg, quote! { numbat_wasm::AsynCallArg::push_async_arg }, quote! {}),
+        }
+    });
+    let call_value_expr = match payment {
+        Some(payment) => payment,
+        None if payable => {
+            // payable endpoints without a #[payment] argument still need a way to receive funds
+            arg_decl.insert(0, quote! { call_value: BigUint });
+            quote! { call_value }
+        },
+        None => quote! { BigUint::zero() },
+    };
+
+    ClientCallArgs {
+        arg_decl,
+        arg_push_snippets,
+        call_value_expr,
+    }
+}
+
+fn method_payable(m: &Method) -> bool {
+    matches!(m.metadata, MethodMetadata::Regular{ payable: true, .. })
+}
+
+fn generate_client_method(m: &Method, endpoint_name: &syn::Ident) -> proc_macro2::TokenStream {
+    let method_name = &m.name;
+    let ClientCallArgs { arg_decl, arg_push_snippets, call_value_expr } = client_call_args(m, method_payable(m));
+
+    let ret_type = match &m.return_type {
+        syn::ReturnType::Default => quote! { () },
+        syn::ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    let endpoint_name_str = endpoint_name.to_string();
+    quote! {
+        pub fn #method_name ( &self , #(#arg_decl),* ) -> Result<<#ret_type as numbat_wasm::EndpointResultDecode>::Decoded, Vec<u8>> {
+            let mut call_data_ser = numbat_wasm::call_data::CallDataSerializer::new(&[]);
+            #(#arg_push_snippets)*
+            let args = numbat_wasm::call_data_args(call_data_ser.as_slice())
+                .map_err(|sc_err| sc_err.as_bytes().to_vec())?;
+            let results = self.caller_api.call_endpoint(&self.caller, &self.address, #call_value_expr, #endpoint_name_str, args)?;
+            numbat_wasm::decode_endpoint_results::<#ret_type>(results)
+                .map_err(|sc_err| sc_err.as_bytes().to_vec())
+        }
+    }
+}
+
+/// Deploys a new instance of the contract, with the init arguments, and returns a client for it.
+/// The deployer becomes the caller.
+fn generate_client_deploy(init: &Method, contract_impl_name: &syn::Path) -> proc_macro2::TokenStream {
+    let ClientCallArgs { arg_decl, arg_push_snippets, call_value_expr } = client_call_args(init, method_payable(init));
+    quote! {
+        pub fn deploy(caller_api: E, deployer: &Address, #(#arg_decl),* ) -> Result<Self, Vec<u8>>
+        where
+            E: ContractHookApi<BigInt, BigUint> + ContractIOApi<BigInt, BigUint> + Clone + 'static,
+        {
+            let mut call_data_ser = numbat_wasm::call_data::CallDataSerializer::new(&[]);
+            #(#arg_push_snippets)*
+            let args = numbat_wasm::call_data_args(call_data_ser.as_slice())
+                .map_err(|sc_err| sc_err.as_bytes().to_vec())?;
+            let contract = #contract_impl_name::new(caller_api.clone());
+            let address = caller_api.deploy_contract(deployer, Box::new(contract), #call_value_expr, args)?;
+            Result::Ok(Self::new(caller_api, deployer, &address))
+        }
+    }
+}
+
+/// Typed client for calling the contract from tests, over any `EndpointCaller`, e.g. the debugger mock.
+/// Each endpoint gets a method taking the endpoint arguments and returning the decoded result, or the error message.
+/// Contracts with an init also get a `deploy` constructor.
+/// Only compiled for native targets.
+pub fn generate_test_client(trait_name: &syn::Ident, contract_impl_name: &syn::Path, methods: &[Method]) -> proc_macro2::TokenStream {
+    let client_name = format_ident!("{}Client", trait_name);
+    let client_methods: Vec<proc_macro2::TokenStream> = methods.iter()
+        .filter(|m| {
+            // their results borrow contract storage, which makes no sense outside the contract
+            !matches!(m.metadata, MethodMetadata::StorageGetMut{ .. })
+        })
+        .filter_map(|m| {
+            m.metadata.endpoint_name()
+                .filter(|endpoint_name| *endpoint_name != "init")
+                .map(|endpoint_name| generate_client_method(m, endpoint_name))
+        })
+        .collect();
+    let client_deploy = methods.iter()
+        .find(|m| matches!(m.metadata.endpoint_name(), Some(endpoint_name) if endpoint_name == "init"))
+        .map(|init| generate_client_deploy(init, contract_impl_name));
+
+    let bi_where = snippets::big_int_where();
+    quote! {
+        #[cfg(not(target_arch = "wasm32"))]
+        pub struct #client_name<E: numbat_wasm::EndpointCaller> {
+            pub caller_api: E,
+            pub caller: Address,
+            pub address: Address,
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl<E: numbat_wasm::EndpointCaller> #client_name<E> {
+            pub fn new(caller_api: E, caller: &Address, address: &Address) -> Self {
+                #client_name {
+                    caller_api,
+                    caller: caller.clone(),
+                    address: address.clone(),
+                }
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl<E, BigInt, BigUint> #client_name<E>
+        #bi_where
+            E: numbat_wasm::EndpointCaller<BigInt = BigInt, BigUint = BigUint>,
+        {
+            #client_deploy
+
+            #(#client_methods)*
+        }
+    }
+}
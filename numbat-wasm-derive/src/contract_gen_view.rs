@@ -0,0 +1,98 @@
+use super::contract_gen_method::*;
+use proc_macro2::{TokenStream, TokenTree, Delimiter};
+use quote::ToTokens;
+
+/// API methods that change state. Views calling them are rejected at compile time.
+static STATE_CHANGING_API_METHODS: &[&str] = &[
+    "storage_store",
+    "storage_store_bytes32",
+    "storage_store_big_uint",
+    "storage_store_big_int",
+    "storage_store_i64",
+    "send_tx",
+    "async_call",
+    "async_call_with_gas",
+    "write_log",
+    "contract_proxy",
+];
+
+/// Free functions that change state.
+static STATE_CHANGING_FUNCTIONS: &[&str] = &[
+    "storage_set",
+    "storage_set_opt",
+    "storage_clear",
+];
+
+/// Rejects views that obviously change state: those calling state-changing API methods,
+/// storage setters, clear and update methods, mutable storage getters or events.
+/// Anything less obvious, e.g. a view calling a private method that writes to storage,
+/// is caught at runtime by the read-only API views get.
+pub fn check_views_read_only(methods: &[Method]) {
+    let state_changing_methods: Vec<String> = methods.iter()
+        .filter_map(|m| match m.metadata {
+            MethodMetadata::Event{ .. } |
+            MethodMetadata::StorageSetter{ .. } |
+            MethodMetadata::StorageSetOpt{ .. } |
+            MethodMetadata::StorageGetMut{ .. } |
+            MethodMetadata::StorageClear{ .. } |
+            MethodMetadata::StorageUpdate{ .. } => Some(m.name.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let is_state_changing_method = |name: &str| {
+        STATE_CHANGING_API_METHODS.contains(&name) ||
+        state_changing_methods.iter().any(|m| m == name)
+    };
+
+    for m in methods.iter().filter(|m| m.metadata.is_view()) {
+        if let Some(body) = &m.body {
+            if let Some(called) = find_state_changing_call(body.to_token_stream(), &is_state_changing_method) {
+                panic!("View `{}` cannot call `{}`, because it changes state.", m.name, called);
+            }
+        }
+    }
+}
+
+fn find_state_changing_call(tokens: TokenStream, is_state_changing_method: &dyn Fn(&str) -> bool) -> Option<String> {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, tree) in trees.iter().enumerate() {
+        match tree {
+            TokenTree::Group(group) => {
+                let found = find_state_changing_call(group.stream(), is_state_changing_method);
+                if found.is_some() {
+                    return found;
+                }
+            },
+            TokenTree::Ident(ident) => {
+                if !is_followed_by_call(&trees, i) {
+                    continue;
+                }
+                let name = ident.to_string();
+                let is_method_call = i > 0 && is_dot(&trees[i - 1]);
+                if is_method_call && is_state_changing_method(name.as_str()) {
+                    return Some(name);
+                }
+                if !is_method_call && STATE_CHANGING_FUNCTIONS.contains(&name.as_str()) {
+                    return Some(name);
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+fn is_dot(tree: &TokenTree) -> bool {
+    match tree {
+        TokenTree::Punct(punct) => punct.as_char() == '.',
+        _ => false,
+    }
+}
+
+fn is_followed_by_call(trees: &[TokenTree], i: usize) -> bool {
+    match trees.get(i + 1) {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+        _ => false,
+    }
+}
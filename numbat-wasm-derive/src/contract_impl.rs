@@ -19,8 +19,39 @@ pub fn contract_implementation(
     let endpoints = contract.generate_endpoints();
     let function_selector_body = contract.generate_function_selector_body();
     let callback_body = contract.generate_callback_body();
+    let is_view_body = contract.generate_is_view_body();
+    let proxy = contract.generate_proxy();
+    let wasm_exports_cfg = contract.generate_wasm_exports_cfg();
+    let wasm_fallback_error = contract.generate_wasm_fallback_error();
+    let test_client = contract.generate_test_client();
+    let storage_keys_const = contract.generate_storage_keys_const();
+    let module_storage_key_check = contract.generate_module_storage_key_check();
     let api_where = snippets::api_where();
 
+    // modules keep their storage prefix, and pass it on to copies of themselves
+    let (storage_prefix_field, storage_prefix_init, storage_prefix_constructor) = if contract.is_module {
+      (
+        quote! { storage_prefix: Vec<u8>, },
+        quote! { storage_prefix: Vec::new(), },
+        quote! {
+          pub fn with_storage_prefix(api: T, storage_prefix: Vec<u8>) -> Self {
+            let mut instance = Self::new(api);
+            instance.storage_prefix = storage_prefix;
+            instance
+          }
+        },
+      )
+    } else {
+      (quote! {}, quote! {}, quote! {})
+    };
+    let new_with_api = |api: proc_macro2::TokenStream| if contract.is_module {
+      quote! { #contract_impl_ident::with_storage_prefix(#api, self.storage_prefix.clone()) }
+    } else {
+      quote! { #contract_impl_ident::new(#api) }
+    };
+    let read_only_instance = new_with_api(quote! { numbat_wasm::ReadOnlyApi::new(self.api.clone()) });
+    let cloned_instance = new_with_api(quote! { self.api.clone() });
+
     let supertrait_impls = contract.generate_supertrait_impls();
     let contract_trait_api_impl = snippets::contract_trait_api_impl(&contract_impl_ident);
 
@@ -45,20 +76,28 @@ pub fn contract_implementation(
       #api_where
       {
           api: T,
+          #storage_prefix_field
           _phantom1: core::marker::PhantomData<BigInt>,
           _phantom2: core::marker::PhantomData<BigUint>,
       }
 
+      #storage_keys_const
+
+      #module_storage_key_check
+
       impl <T, BigInt, BigUint> #contract_impl_ident<T, BigInt, BigUint>
       #api_where
       {
         pub fn new(api: T) -> Self {
           #contract_impl_ident {
             api,
+            #storage_prefix_init
             _phantom1: core::marker::PhantomData,
             _phantom2: core::marker::PhantomData,
           }
         }
+
+        #storage_prefix_constructor
       }
 
       #contract_trait_api_impl
@@ -84,48 +123,66 @@ pub fn contract_implementation(
       #api_where
       {
         #(#call_methods)*
+
+        /// Instance given to views, which cannot change state.
+        #[allow(dead_code)]
+        fn ___read_only(&self) -> #contract_impl_ident<numbat_wasm::ReadOnlyApi<T>, BigInt, BigUint> {
+          #read_only_instance
+        }
       }
 
+      #proxy
+
     };
 
-    if wasm32_mode() {
-      // release mode adds endpoints for wasmer 
-      quote! {
-        #main_definition
+    // both variants are generated, the target decides which one gets compiled, regardless of the profile
+    // wasm32 builds get the endpoints for wasmer
+    // native builds get the contract interface, that we use for the mocks,
+    // which relies on "call" methods with no parameter and a function selector
+    quote! {
+      #main_definition
 
-        use numbat_wasm_node::{AndesBigInt, AndesBigUint};
-        use numbat_wasm_node::*;
+      #[cfg(target_arch = "wasm32")]
+      use numbat_wasm_node::{AndesBigInt, AndesBigUint};
+      #[cfg(target_arch = "wasm32")]
+      use numbat_wasm_node::*;
 
-        fn new_andes_instance() -> #contract_impl_ident<AndesApiImpl, AndesBigInt, AndesBigUint> {
-          let api = AndesApiImpl{};
-          #contract_impl_ident::new(api)
-        }
+      #wasm_fallback_error
 
-        #[allow(non_snake_case)]
-        mod endpoints {
-          use super::*;
+      #wasm_exports_cfg
+      fn new_andes_instance() -> #contract_impl_ident<AndesApiImpl, AndesBigInt, AndesBigUint> {
+        let api = AndesApiImpl{};
+        #contract_impl_ident::new(api)
+      }
 
-          #(#endpoints)*
-        }
+      #wasm_exports_cfg
+      #[allow(non_snake_case)]
+      mod endpoints {
+        use super::*;
+
+        #(#endpoints)*
       }
-    } else {
-      // debug mode adds the contract interface, that we use for the mocks
-      // this interface also relies on "call" methods with no parameter and a function selector
-      quote! {
-        #main_definition
-  
-        use numbat_wasm::CallableContract;
-        impl <T, BigInt, BigUint> CallableContract for #contract_impl_ident<T, BigInt, BigUint> 
-        #api_where
-        {
-          fn call(&self, fn_name: &'static str) {
-            #function_selector_body
-          }
-  
-          fn clone_contract(&self) -> Box<dyn CallableContract> {
-            Box::new(#contract_impl_ident::new(self.api.clone()))
-          }
+
+      #[cfg(not(target_arch = "wasm32"))]
+      use numbat_wasm::CallableContract;
+      #[cfg(not(target_arch = "wasm32"))]
+      impl <T, BigInt, BigUint> CallableContract for #contract_impl_ident<T, BigInt, BigUint> 
+      #api_where
+      {
+        fn call(&self, fn_name: &'static str) {
+          #function_selector_body
+        }
+
+        #[allow(unused_variables)]
+        fn is_view(&self, fn_name: &str) -> bool {
+          #is_view_body
+        }
+
+        fn clone_contract(&self) -> Box<dyn CallableContract> {
+          Box::new(#cloned_instance)
         }
       }
+
+      #test_client
     }
 }
@@ -13,29 +13,19 @@ pub fn process_contract(
     let contract = Contract::new(args_input, proc_input);
 
     let contract_impl = contract_implementation(&contract);
+    let wasm_exports_cfg = contract.generate_wasm_exports_cfg();
 
-    if wasm32_mode() {
-        // release mode adds endpoints for wasmer 
-        proc_macro::TokenStream::from(quote! {
-            #[macro_use]
-            extern crate numbat_wasm;
-    
-            #contract_impl
+    proc_macro::TokenStream::from(quote! {
+        #[macro_use]
+        extern crate numbat_wasm;
 
-            #[no_mangle]
-            pub fn callBack () {
-            let inst = new_andes_instance();
-            inst.callback();
-            }
-        })
-      } else {
-        // debug mode adds the contract interface, that we use for the mocks
-        // this interface also relies on "call" methods with no parameter and a function selector
-        proc_macro::TokenStream::from(quote! {
-            #[macro_use]
-            extern crate numbat_wasm;
-    
-            #contract_impl
-        })
-      }
-}
\ No newline at end of file
+        #contract_impl
+
+        #wasm_exports_cfg
+        #[no_mangle]
+        pub fn callBack () {
+          let inst = new_andes_instance();
+          inst.callback();
+        }
+    })
+}
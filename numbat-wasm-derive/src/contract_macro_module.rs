@@ -10,7 +10,7 @@ pub fn process_module(
     let args_input = parse_macro_input!(args as syn::AttributeArgs);
     let proc_input = &parse_macro_input!(input as syn::ItemTrait);
 
-    let contract = Contract::new(args_input, proc_input);
+    let contract = Contract::new_module(args_input, proc_input);
 
     let contract_impl = contract_implementation(&contract);
 
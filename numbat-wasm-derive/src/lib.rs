@@ -20,12 +20,17 @@ mod arg_regular;
 mod arg_str_serialize;
 mod contract_gen;
 mod contract_gen_callback;
+mod contract_gen_collisions;
 mod contract_gen_event;
 mod contract_gen_finish;
+mod contract_gen_label;
 mod contract_gen_method;
 mod contract_gen_module;
 mod contract_gen_payable;
+mod contract_gen_proxy;
 mod contract_gen_storage;
+mod contract_gen_test_client;
+mod contract_gen_view;
 mod contract_impl;
 mod contract_macro_main;
 mod contract_macro_module;
@@ -37,21 +42,6 @@ mod reserved;
 mod callable;
 mod callable_gen;
 
-fn wasm32_mode() -> bool {
-  // this checks if we set --release or not in the command line
-  // we should always set --release when building sc wasm and never when running the debugger, so this works
-  let debug_mode = cfg!(debug_assertions);
-  !debug_mode
-
-  // this is supposed to check whether or not the target starts with "wasm32-...
-  // for some reason this no longer works, TODO: investigate
-  //cfg!(target_arch = "wasm32")
-
-  // when debugging the macro output, the above methods don't seem to work
-  // so just temporarily hardcode while bugfixing
-  //true
-}
-
 #[proc_macro_attribute]
 pub fn contract(
   args: proc_macro::TokenStream,
@@ -13,7 +13,15 @@ static ATTR_MULTI: &str = "multi";
 static ATTR_STORAGE_GET: &str = "storage_get";
 static ATTR_STORAGE_SET: &str = "storage_set";
 static ATTR_STORAGE_GET_MUT: &str = "storage_get_mut";
+static ATTR_STORAGE_GET_OPT: &str = "storage_get_opt";
+static ATTR_STORAGE_SET_OPT: &str = "storage_set_opt";
+static ATTR_STORAGE_IS_EMPTY: &str = "storage_is_empty";
+static ATTR_STORAGE_CLEAR: &str = "storage_clear";
+static ATTR_STORAGE_UPDATE: &str = "storage_update";
 static ATTR_MODULE: &str = "module";
+static ATTR_STORAGE_PREFIX: &str = "storage_prefix";
+static ATTR_LABEL: &str = "label";
+static ATTR_FALLBACK: &str = "fallback";
 
 fn has_attribute(attrs: &[syn::Attribute], name: &str) -> bool {
 	attrs.iter().any(|attr| {
@@ -36,6 +44,10 @@ pub fn is_init(m: &syn::TraitItemMethod) -> bool {
     has_attribute(&m.attrs, ATTR_INIT)
 }
 
+pub fn is_fallback(m: &syn::TraitItemMethod) -> bool {
+    has_attribute(&m.attrs, ATTR_FALLBACK)
+}
+
 pub fn is_payable(m: &syn::TraitItemMethod) -> bool {
     has_attribute(&m.attrs, ATTR_PAYABLE)
 }
@@ -60,39 +72,52 @@ fn find_attr_one_string_arg(m: &syn::TraitItemMethod, attr_name: &str) -> Option
             false
         }
     });
-    match event_attr {        
-        None => None,
-        Some(attr) => {
-            let result_str: String;
-            let mut iter = attr.clone().tokens.into_iter();
-            match iter.next() {
-                Some(proc_macro2::TokenTree::Group(group)) => {
-                    if group.delimiter() != proc_macro2::Delimiter::Parenthesis {
-                        panic!("event paranthesis expected");
-                    }
-                    let mut iter2 = group.stream().into_iter();
-                    match iter2.next() {
-                        Some(proc_macro2::TokenTree::Literal(lit)) => {
-                            let str_val = lit.to_string();
-                            if !str_val.starts_with('\"') || !str_val.ends_with('\"') {
-                                panic!("string literal expected as attribute argument");
-                            }
-                            let substr = &str_val[1..str_val.len()-1];
-                            result_str = substr.to_string();
-                        },
-                        _ => panic!("literal expected as event identifier")
-                    }
-                },
-                _ => panic!("missing event identifier")
+    event_attr.map(attr_one_string_arg)
+}
+
+/// Finds all method attributes with given name, each with 1 single string argument.
+fn find_all_attr_one_string_arg(m: &syn::TraitItemMethod, attr_name: &str) -> Vec<String> {
+    m.attrs.iter()
+        .filter(|attr| {
+            if let Some(first_seg) = attr.path.segments.first() {
+                first_seg.ident == attr_name
+            } else {
+                false
             }
+        })
+        .map(attr_one_string_arg)
+        .collect()
+}
 
-            if iter.next().is_some() {
-                panic!("event too many tokens in event attribute");
+fn attr_one_string_arg(attr: &syn::Attribute) -> String {
+    let result_str: String;
+    let mut iter = attr.clone().tokens.into_iter();
+    match iter.next() {
+        Some(proc_macro2::TokenTree::Group(group)) => {
+            if group.delimiter() != proc_macro2::Delimiter::Parenthesis {
+                panic!("event paranthesis expected");
+            }
+            let mut iter2 = group.stream().into_iter();
+            match iter2.next() {
+                Some(proc_macro2::TokenTree::Literal(lit)) => {
+                    let str_val = lit.to_string();
+                    if !str_val.starts_with('\"') || !str_val.ends_with('\"') {
+                        panic!("string literal expected as attribute argument");
+                    }
+                    let substr = &str_val[1..str_val.len()-1];
+                    result_str = substr.to_string();
+                },
+                _ => panic!("literal expected as event identifier")
             }
+        },
+        _ => panic!("missing event identifier")
+    }
 
-            Some(result_str)
-        }
+    if iter.next().is_some() {
+        panic!("event too many tokens in event attribute");
     }
+
+    result_str
 }
 
 pub struct EventAttribute {
@@ -166,6 +191,88 @@ impl StorageGetMutAttribute {
     }
 }
 
+pub struct StorageGetOptAttribute {
+    pub identifier: String
+}
+
+impl StorageGetOptAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StorageGetOptAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_GET_OPT)
+            .map(|identifier| StorageGetOptAttribute{ identifier })
+    }
+}
+
+pub struct StorageSetOptAttribute {
+    pub identifier: String
+}
+
+impl StorageSetOptAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StorageSetOptAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_SET_OPT)
+            .map(|identifier| StorageSetOptAttribute{ identifier })
+    }
+}
+
+pub struct StorageIsEmptyAttribute {
+    pub identifier: String
+}
+
+impl StorageIsEmptyAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StorageIsEmptyAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_IS_EMPTY)
+            .map(|identifier| StorageIsEmptyAttribute{ identifier })
+    }
+}
+
+pub struct StorageClearAttribute {
+    pub identifier: String
+}
+
+impl StorageClearAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StorageClearAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_CLEAR)
+            .map(|identifier| StorageClearAttribute{ identifier })
+    }
+}
+
+pub struct StorageUpdateAttribute {
+    pub identifier: String
+}
+
+impl StorageUpdateAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StorageUpdateAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_UPDATE)
+            .map(|identifier| StorageUpdateAttribute{ identifier })
+    }
+}
+
+/// Prefix added to all storage keys of a module instance.
+pub struct StoragePrefixAttribute {
+    pub prefix: String
+}
+
+impl StoragePrefixAttribute {
+    pub fn parse(m: &syn::TraitItemMethod) -> Option<StoragePrefixAttribute> {
+        find_attr_one_string_arg(m, ATTR_STORAGE_PREFIX)
+            .map(|prefix| StoragePrefixAttribute{ prefix })
+    }
+}
+
+/// Selects the wasm outputs that export an endpoint.
+/// An endpoint can have several labels, each in its own attribute.
+pub struct LabelAttribute {
+    pub label: String
+}
+
+impl LabelAttribute {
+    pub fn parse_all(m: &syn::TraitItemMethod) -> Vec<LabelAttribute> {
+        find_all_attr_one_string_arg(m, ATTR_LABEL)
+            .into_iter()
+            .map(|label| LabelAttribute{ label })
+            .collect()
+    }
+}
+
 /// Finds a method attribute with given name and 1 single optional argument.
 /// In the result, the first option is for the attribute, the second for the argument.
 fn find_attr_with_one_opt_token_tree_arg(m: &syn::TraitItemMethod, attr_name: &str) -> Option<Option<proc_macro2::TokenTree>> {
@@ -142,6 +142,21 @@ pub fn contract_trait_api_impl(contract_struct: &syn::Path) -> proc_macro2::Toke
           self.api.async_call(to, amount, data);
         }
 
+        #[inline]
+        fn async_call_with_gas(&self, to: &Address, amount: &BigUint, data: &[u8], gas_limit: u64) {
+          self.api.async_call_with_gas(to, amount, data, gas_limit);
+        }
+
+        #[inline]
+        fn get_num_async_calls(&self) -> u32 {
+          self.api.get_num_async_calls()
+        }
+
+        #[inline]
+        fn get_callback_call_index(&self) -> u32 {
+          self.api.get_callback_call_index()
+        }
+
         #[inline]
         fn get_tx_hash(&self) -> H256 {
           self.api.get_tx_hash()
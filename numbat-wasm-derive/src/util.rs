@@ -25,6 +25,28 @@ pub fn extract_struct_name(args: syn::AttributeArgs) -> syn::Path {
     }
 }
 
+/// Splits off the optional `proxy(ProxyName)` argument of the contract annotation.
+pub fn extract_proxy_name(args: syn::AttributeArgs) -> (Option<syn::Ident>, syn::AttributeArgs) {
+    let mut proxy_name = None;
+    let mut other_args = syn::AttributeArgs::new();
+    for arg in args.into_iter() {
+        match &arg {
+            syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("proxy") => {
+                if proxy_name.is_some() {
+                    panic!("Only one proxy can be declared.");
+                }
+                match list.nested.iter().collect::<Vec<_>>().as_slice() {
+                    [syn::NestedMeta::Meta(syn::Meta::Path(path))] if path.get_ident().is_some() =>
+                        proxy_name = path.get_ident().cloned(),
+                    _ => panic!("Proxy trait name expected, e.g. `proxy(AdderProxy)`."),
+                }
+            },
+            _ => other_args.push(arg),
+        }
+    }
+    (proxy_name, other_args)
+}
+
 pub fn extract_methods(contract_trait: &syn::ItemTrait) -> Vec<syn::TraitItemMethod> {
     contract_trait
         .items
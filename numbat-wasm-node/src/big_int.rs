@@ -1,6 +1,7 @@
 
 
 use crate::big_uint::*;
+use crate::ext_error;
 
 use core::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use core::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
@@ -9,7 +10,9 @@ use core::cmp::Ordering;
 use alloc::vec::Vec;
 
 use numbat_wasm::BigIntApi;
+use numbat_wasm::BigUintApi;
 use numbat_wasm::Sign;
+use numbat_wasm::err_msg;
 
 extern {
     fn bigIntNew(value: i64) -> i32;
@@ -217,6 +220,12 @@ impl Encode for AndesBigInt {
     }
 }
 
+impl TypeDescription for AndesBigInt {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::BigInt
+    }
+}
+
 impl Decode for AndesBigInt {
     const TYPE_INFO: TypeInfo = TypeInfo::BigInt;
     
@@ -269,4 +278,48 @@ impl BigIntApi<AndesBigUint> for AndesBigInt {
             AndesBigInt{ handle }
         }
     }
+
+    fn pow(&self, exp: u32) -> Self {
+        // square-and-multiply, the VM has no exponentiation hook
+        let mut result = AndesBigInt::from(1i64);
+        let mut base = self.clone();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    fn sqrt(&self) -> Self {
+        if unsafe { bigIntSign(self.handle) } < 0 {
+            ext_error::signal_error(err_msg::BIG_INT_SQRT_NEGATIVE)
+        }
+        AndesBigInt::from(self.abs_uint().sqrt())
+    }
+
+    fn log2(&self) -> Option<u32> {
+        if unsafe { bigIntSign(self.handle) } > 0 {
+            self.abs_uint().log2()
+        } else {
+            None
+        }
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        let bytes = self.to_signed_bytes_be();
+        if bytes.len() > 8 {
+            return None;
+        }
+        // sign-extend to 8 bytes
+        let fill = if unsafe { bigIntSign(self.handle) } < 0 { 0xffu8 } else { 0u8 };
+        let mut extended = [fill; 8];
+        extended[8 - bytes.len() ..].copy_from_slice(&bytes);
+        Some(i64::from_be_bytes(extended))
+    }
 }
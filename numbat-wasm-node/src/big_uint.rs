@@ -266,6 +266,12 @@ impl Encode for AndesBigUint {
     }
 }
 
+impl TypeDescription for AndesBigUint {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::BigUint
+    }
+}
+
 impl Decode for AndesBigUint {
     const TYPE_INFO: TypeInfo = TypeInfo::BigUint;
 
@@ -331,4 +337,69 @@ impl BigUintApi for AndesBigUint {
             AndesBigUint{ handle }
         }
     }
+
+    fn pow(&self, exp: u32) -> Self {
+        // square-and-multiply, the VM has no exponentiation hook
+        let mut result = AndesBigUint::from(1u64);
+        let mut base = self.clone();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    fn sqrt(&self) -> Self {
+        // Newton's method, starting from a power of 2 that is at least the root
+        let log = match self.log2() {
+            Some(log) => log,
+            None => return AndesBigUint::from(0u64),
+        };
+        let mut x = AndesBigUint::from(1u64) << (log as usize / 2 + 1);
+        loop {
+            let y = (&x + &(self / &x)) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn log2(&self) -> Option<u32> {
+        let bytes = self.to_bytes_be();
+        if bytes.is_empty() {
+            return None;
+        }
+        // the VM does not return leading zero bytes
+        let top_bits = 8 - bytes[0].leading_zeros();
+        Some((bytes.len() as u32 - 1) * 8 + top_bits - 1)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        unsafe {
+            let result = bigIntNew(0);
+            bigIntSub(result, self.handle, other.handle);
+            if bigIntSign(result) < 0 {
+                None
+            } else {
+                Some(AndesBigUint {handle: result})
+            }
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        if self.byte_length() > 8 {
+            return None;
+        }
+        let mut bytes = [0u8; 8];
+        let byte_len = self.byte_length() as usize;
+        self.copy_to_slice_big_endian(&mut bytes[8 - byte_len ..]);
+        Some(u64::from_be_bytes(bytes))
+    }
 }
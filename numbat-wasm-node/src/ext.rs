@@ -230,6 +230,22 @@ impl numbat_wasm::ContractHookApi<AndesBigInt, AndesBigUint> for AndesApiImpl {
         }
     }
 
+    /// `asyncCall` always gives the call all the remaining gas.
+    fn async_call_with_gas(&self, _to: &Address, _amount: &AndesBigUint, _data: &[u8], _gas_limit: u64) {
+        ext_error::signal_error(err_msg::ASYNC_CALL_GAS_LIMIT_NOT_SUPPORTED)
+    }
+
+    /// `asyncCall` ends the execution, so no async call can come before another.
+    #[inline]
+    fn get_num_async_calls(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn get_callback_call_index(&self) -> u32 {
+        0
+    }
+
     #[inline]
     fn get_tx_hash(&self) -> H256 {
         unsafe {
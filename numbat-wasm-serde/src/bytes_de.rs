@@ -41,6 +41,17 @@ where
     }
 }
 
+/// Deserializes a value in nested form from the start of the input,
+/// also returning the number of bytes it took up.
+pub(crate) fn from_bytes_nested<'a, T>(bytes: &'a [u8]) -> Result<(T, usize)>
+where
+    T: Deserialize<'a>,
+{
+    let mut deserializer = ErdDeserializer { input: bytes, top_level: false };
+    let t = T::deserialize(&mut deserializer)?;
+    Ok((t, bytes.len() - deserializer.input.len()))
+}
+
 /// Handles both signed and unsigned of any length.
 /// No generics here, because we want the executable binary as small as possible.
 pub fn bytes_to_number(bytes: &[u8], signed: bool) -> u64 {
@@ -90,6 +101,29 @@ impl<'de> ErdDeserializer<'de> {
         self.input = &[];
         bytes
     }
+
+    /// Top level byte slices take up the entire input,
+    /// nested ones are prefixed by their length.
+    fn next_byte_slice(&mut self) -> Result<&'de [u8]> {
+        if self.top_level {
+            Ok(self.flush())
+        } else {
+            let size_bytes = self.next_bytes(USIZE_SIZE)?;
+            let size = bytes_to_number(size_bytes, false) as usize;
+            self.next_bytes(size)
+        }
+    }
+
+    /// Number of items in a nested sequence or map, read from its length prefix.
+    /// Top level sequences have no prefix, they end when the input runs out.
+    fn next_items_hint(&mut self) -> Result<Option<usize>> {
+        if self.top_level {
+            Ok(None)
+        } else {
+            let size_bytes = self.next_bytes(USIZE_SIZE)?;
+            Ok(Some(bytes_to_number(size_bytes, false) as usize))
+        }
+    }
 }
 
 macro_rules! impl_nums {
@@ -99,7 +133,11 @@ macro_rules! impl_nums {
             where V: serde::de::Visitor<'de>,
         {
             let bytes = if self.top_level {
-                self.flush()
+                let bytes = self.flush();
+                if bytes.len() > $num_bytes {
+                    return Err(SDError::InputTooLong);
+                }
+                bytes
             } else {
                 self.next_bytes($num_bytes)?
             };
@@ -125,14 +163,12 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
         V: serde::de::Visitor<'de>,
     {
         if self.top_level {
-            // top level bool is either [1] or []
-            if !self.input.is_empty() {
-                match self.next_byte()? {
-                    1 => visitor.visit_bool(true),
-                    _ => Err(SDError::InvalidValue)
-                }
-            } else {
-                visitor.visit_bool(false)
+            // top level bool is normally either [1] or [], but [0] is also accepted
+            match self.flush() {
+                [] | [0] => visitor.visit_bool(false),
+                [1] => visitor.visit_bool(true),
+                [_] => Err(SDError::InvalidValue),
+                _ => Err(SDError::InputTooLong),
             }
         } else {
             // regular bool is either [1] or [0]
@@ -186,39 +222,37 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
         Err(SDError::UnsupportedOperation)
     }
 
-    fn deserialize_str<V>(self, _: V) -> Result<V::Value>
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SDError::UnsupportedOperation)
+        let bytes = self.next_byte_slice()?;
+        match core::str::from_utf8(bytes) {
+            Ok(s) => visitor.visit_borrowed_str(s),
+            Err(_) => Err(SDError::InvalidValue),
+        }
     }
 
-    fn deserialize_string<V>(self, _: V) -> Result<V::Value>
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SDError::UnsupportedOperation)
+        self.deserialize_str(visitor)
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        let bytes = if self.top_level {
-            self.flush()
-        } else {
-            let size_bytes = self.next_bytes(USIZE_SIZE)?;
-            let size = bytes_to_number(size_bytes, false) as usize;
-            self.next_bytes(size)?
-        };
+        let bytes = self.next_byte_slice()?;
         visitor.visit_borrowed_bytes(bytes)
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SDError::UnsupportedOperation)
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_enum<V>(
@@ -249,6 +283,10 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
+        if self.top_level && self.input.is_empty() {
+            // top level None is serialized as empty bytes
+            return visitor.visit_none();
+        }
         self.top_level = false;
         let value = self.next_byte()?;
         match value {
@@ -262,14 +300,7 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        let remaining_items_hint = if self.top_level {
-            None // we will know we ran out of items when the input runs out
-        } else {
-            let size_bytes = self.next_bytes(USIZE_SIZE)?;
-            let size = bytes_to_number(size_bytes, false) as usize;
-            Some(size)
-        };
-
+        let remaining_items_hint = self.next_items_hint()?;
         self.top_level = false;
         visitor.visit_seq(Access {
             deserializer: self,
@@ -277,11 +308,17 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
         })
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value>
+    /// Maps are length-prefixed sequences of key-value pairs.
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SDError::NotImplemented)
+        let remaining_items_hint = self.next_items_hint()?;
+        self.top_level = false;
+        visitor.visit_map(Access {
+            deserializer: self,
+            remaining_items_hint,
+        })
     }
 
     fn deserialize_struct<V>(
@@ -307,6 +344,7 @@ impl<'de> serde::Deserializer<'de> for &mut ErdDeserializer<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
+        self.top_level = false;
         visitor.visit_newtype_struct(self)
     }
 
@@ -408,10 +446,12 @@ impl<'a, 'de> serde::de::SeqAccess<'de> for Access<'a, 'de> {
                 }
             },
             None => {
-                match seed.deserialize(&mut *self.deserializer) {
-                    Ok(value) => Ok(Some(value)),
-                    Err(SDError::InputTooShort) => Ok(None),
-                    Err(other_err) => Err(other_err),
+                // top level sequence, ends when the input runs out
+                if self.deserializer.input.is_empty() {
+                    Ok(None)
+                } else {
+                    let value = seed.deserialize(&mut *self.deserializer)?;
+                    Ok(Some(value))
                 }
             }
         }
@@ -423,11 +463,35 @@ impl<'a, 'de> serde::de::SeqAccess<'de> for Access<'a, 'de> {
     }
 }
 
+impl<'a, 'de> serde::de::MapAccess<'de> for Access<'a, 'de> {
+    type Error = SDError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        // each key starts a new entry, so entries are counted just like sequence items
+        serde::de::SeqAccess::next_element_seed(self, seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining_items_hint
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_struct::*;
     use alloc::vec::Vec;
     use core::fmt::Debug;
 
@@ -463,13 +527,6 @@ mod tests {
 
     #[test]
     fn test_struct() {
-        #[derive(Deserialize, PartialEq, Debug)]
-        struct Test {
-            int: u16,
-            seq: Vec<u8>,
-            another_byte: u8,
-        }
-
         let test = Test {
             int: 1,
             seq: [5, 6].to_vec(),
@@ -479,15 +536,46 @@ mod tests {
     }
 
     #[test]
-    fn test_enum() {
-        #[derive(Deserialize, Hash, Eq, PartialEq, Clone, Debug)]
-        enum E {
-            Unit,
-            Newtype(u32),
-            Tuple(u32, u32),
-            Struct { a: u32 },
+    fn test_str() {
+        use alloc::string::String;
+
+        assert_eq!(from_bytes::<&str>(b"abc").unwrap(), "abc");
+        deser_ok(String::from("abc"), b"abc");
+        deser_ok(String::new(), &[]);
+        deser_ok((1u8, String::from("abc")), &[1, 0, 0, 0, 3, b'a', b'b', b'c']);
+        assert!(from_bytes::<String>(&[0xff, 0xfe]).is_err());
+        assert!(from_bytes::<(String, u8)>(&[0, 0, 0, 3, b'a', b'b']).is_err());
+    }
+
+    #[test]
+    fn test_map() {
+        use alloc::collections::BTreeMap;
+
+        let mut map = BTreeMap::new();
+        map.insert(1u8, 5u16);
+        map.insert(2u8, 3u16);
+        deser_ok(map.clone(), &[1, 0, 5, 2, 0, 3]);
+        deser_ok(Some(map), &[1, /*size*/ 0, 0, 0, 2, 1, 0, 5, 2, 0, 3]);
+        deser_ok(BTreeMap::<u8, u16>::new(), &[]);
+        assert!(from_bytes::<Option<BTreeMap<u8, u16>>>(&[1, 0, 0, 0, 2, 1, 0, 5]).is_err());
+    }
+
+    #[test]
+    fn test_any_unsupported() {
+        // the format is not self-describing, so types that need deserialize_any fail instead of decoding garbage
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        #[allow(dead_code)]
+        enum Untagged {
+            Number(u32),
+            Bytes(Vec<u8>),
         }
 
+        assert!(matches!(from_bytes::<Untagged>(&[0, 0, 0, 5]), Err(SDError::UnsupportedOperation)));
+    }
+
+    #[test]
+    fn test_enum() {
         let u = E::Unit;
         let expected: &[u8] = &[/*variant index*/ 0, 0, 0, 0];
         deser_ok(u, expected);
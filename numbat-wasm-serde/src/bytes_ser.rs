@@ -25,6 +25,19 @@ where
     Ok(serializer.output)
 }
 
+/// Serializes a value in nested form, i.e. as it would appear inside another structure.
+pub(crate) fn to_bytes_nested<T>(value: T) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut serializer = ErdSerializer {
+        output: Vec::new(),
+        top_level: false,
+    };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
 /// Temporary solution to serialize u64 until wasm compilation issue is fixed.
 pub fn u64_to_bytes(v: u64) -> Vec<u8> {
     let mut serializer = ErdSerializer {
@@ -167,11 +180,9 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
         Err(SDError::NotImplemented)
     }
 
-    // This only works for strings that don't require escape sequences but you
-    // get the idea. For example it would emit invalid JSON if the input string
-    // contains a '"' character.
-    fn serialize_str(self, _v: &str) -> Result<()> {
-        Err(SDError::NotImplemented)
+    // Strings are serialized as their UTF-8 bytes, same as in numbat-codec.
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
     }
 
     // Serialize a byte array as an array of bytes. Could also use a base64
@@ -188,7 +199,11 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
     }
 
     fn serialize_none(self) -> Result<()> {
-        self.push_byte(0u8); // one byte of 0 indicates that nothing comes after
+        if !self.top_level {
+            // one byte of 0 indicates that nothing comes after
+            // at top level, None is simply empty output, just like in numbat-codec
+            self.push_byte(0u8);
+        }
         Ok(())
     }
 
@@ -227,8 +242,8 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
         Ok(())
     }
 
-    // As is done here, serializers are encouraged to treat newtype structs as
-    // insignificant wrappers around the data they contain.
+    // Newtype structs are structs with a single field,
+    // so the field is always serialized in nested form, same as in numbat-codec.
     fn serialize_newtype_struct<T>(
         self,
         _name: &'static str,
@@ -237,6 +252,7 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
     where
         T: ?Sized + Serialize,
     {
+        self.top_level = false;
         value.serialize(self)
     }
 
@@ -294,13 +310,13 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
         Ok(self)
     }
 
-    // Tuple structs look just like sequences in JSON.
+    // Tuple structs have a fixed number of fields, so just like tuples they get no length prefix.
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        self.serialize_seq(Some(len))
+        self.serialize_tuple(len)
     }
 
     // Tuple variants are represented in JSON as `{ NAME: [DATA...] }`. Again
@@ -317,11 +333,10 @@ impl<'a> ser::Serializer for &'a mut ErdSerializer {
         Ok(self)
     }
 
-    // Maps are represented in JSON as `{ K: V, K: V, ... }`.
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        // self.output += "{";
-        // Ok(self)
-        Err(SDError::NotImplemented)
+    // Maps are serialized as a sequence of key-value pairs,
+    // so they are encoded the same way as a `Vec<(K, V)>` in numbat-codec.
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.serialize_seq(len)
     }
 
     // Structs look just like maps in JSON. In particular, JSON requires that we
@@ -443,22 +458,14 @@ impl<'a> ser::SerializeTupleVariant for &'a mut ErdSerializer {
 // same time so `SerializeMap` implementations are required to support
 // `serialize_key` and `serialize_value` individually.
 //
-// There is a third optional method on the `SerializeMap` trait. The
-// `serialize_entry` method allows serializers to optimize for the case where
-// key and value are both available simultaneously. In JSON it doesn't make a
-// difference so the default behavior for `serialize_entry` is fine.
+// Keys and values are simply written one after the other, both in nested form.
+// The entry count was already written by `serialize_map`.
 impl<'a> ser::SerializeMap for &'a mut ErdSerializer {
     type Ok = ();
     type Error = SDError;
 
-    // The Serde data model allows map keys to be any serializable type. JSON
-    // only allows string keys so the implementation below will produce invalid
-    // JSON if the key serializes as something other than a string.
-    //
-    // A real JSON serializer would need to validate that map keys are strings.
-    // This can be done by using a different Serializer to serialize the key
-    // (instead of `&mut **self`) and having that other serializer only
-    // implement `serialize_str` and return an error on any other data type.
+    // The Serde data model allows map keys to be any serializable type,
+    // they get serialized just like any other nested value.
     fn serialize_key<T>(&mut self, key: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
@@ -466,9 +473,6 @@ impl<'a> ser::SerializeMap for &'a mut ErdSerializer {
         key.serialize(&mut **self)
     }
 
-    // It doesn't make a difference whether the colon is printed at the end of
-    // `serialize_key` or at the beginning of `serialize_value`. In this case
-    // the code is a bit simpler having it here.
     fn serialize_value<T>(&mut self, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
@@ -522,6 +526,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut ErdSerializer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_struct::*;
     use core::fmt::Debug;
 
     fn ser_ok<V>(element: V, bytes: &[u8])
@@ -587,13 +592,6 @@ mod tests {
 
     #[test]
     fn test_struct() {
-        #[derive(Serialize, PartialEq, Debug)]
-        struct Test {
-            int: u16,
-            seq: Vec<u8>,
-            another_byte: u8,
-        }
-
         let test = Test {
             int: 1,
             seq: [5, 6].to_vec(),
@@ -614,15 +612,36 @@ mod tests {
     }
 
     #[test]
-    fn test_enum() {
-        #[derive(Serialize, Hash, Eq, PartialEq, Clone, Debug)]
-        enum E {
-            Unit,
-            Newtype(u32),
-            Tuple(u32, u32),
-            Struct { a: u32 },
-        }
+    fn test_top_none() {
+        ser_ok(Option::<u32>::None, &[]);
+        ser_ok(Some(Option::<u32>::None), &[1, 0]);
+    }
+
+    #[test]
+    fn test_newtype_and_tuple_struct() {
+        ser_ok(Newtype(5), &[0, 0, 0, 5]);
+        ser_ok(Pair(1, 2), &[0, 1, 2]);
+        ser_ok(alloc::vec![Pair(1, 2)], &[0, 1, 2]);
+    }
 
+    #[test]
+    fn test_str() {
+        ser_ok("abc", b"abc");
+        ser_ok(alloc::string::String::from("abc"), b"abc");
+        ser_ok((1u8, "abc"), &[1, 0, 0, 0, 3, b'a', b'b', b'c']);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut map = alloc::collections::BTreeMap::new();
+        map.insert(2u8, 3u16);
+        map.insert(1u8, 5u16);
+        ser_ok(map.clone(), &[1, 0, 5, 2, 0, 3]);
+        ser_ok(Some(map), &[1, /*size*/ 0, 0, 0, 2, 1, 0, 5, 2, 0, 3]);
+    }
+
+    #[test]
+    fn test_enum() {
         let u = E::Unit;
         let expected: &[u8] = &[/*variant index*/ 0, 0, 0, 0];
         ser_ok(u, expected);
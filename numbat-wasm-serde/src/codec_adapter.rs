@@ -0,0 +1,224 @@
+use serde::{Serialize, de::DeserializeOwned};
+use numbat_codec::*;
+use alloc::vec::Vec;
+
+use super::bytes_ser::{to_bytes, to_bytes_nested};
+use super::bytes_de::{from_bytes, from_bytes_nested};
+use super::bytes_err::SDError;
+
+/// Error returned when a serde value is nested in an input that cannot be inspected ahead of decoding.
+const INPUT_NOT_PEEKABLE: &[u8] = b"serde value requires peekable input";
+
+/// Wrapper that allows any serde type to be used wherever numbat-codec types are accepted,
+/// e.g. as storage values, endpoint arguments or results.
+///
+/// The value is encoded with `ErdSerializer`, which produces the same bytes as numbat-codec
+/// for equivalent types, so both can be mixed freely.
+/// The exception are `usize` and `isize` fields, which need the `usize32` and `isize32` helpers
+/// to be encoded on 32 bits like numbat-codec does.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct SerdeCodec<T>(pub T);
+
+impl<T> SerdeCodec<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for SerdeCodec<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        SerdeCodec(value)
+    }
+}
+
+impl<T> core::ops::Deref for SerdeCodec<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for SerdeCodec<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl From<SDError> for EncodeError {
+    fn from(sd_err: SDError) -> Self {
+        match sd_err {
+            SDError::UnsupportedOperation => EncodeError::UnsupportedOperation,
+            _ => EncodeError::Dynamic(sd_err.err_msg_bytes().to_vec()),
+        }
+    }
+}
+
+impl From<SDError> for DecodeError {
+    fn from(sd_err: SDError) -> Self {
+        match sd_err {
+            SDError::InputTooShort => DecodeError::InputTooShort,
+            SDError::InputTooLong => DecodeError::InputTooLong,
+            SDError::InvalidValue => DecodeError::InvalidValue,
+            SDError::UnsupportedOperation => DecodeError::UnsupportedOperation,
+            _ => DecodeError::Dynamic(sd_err.err_msg_bytes().to_vec()),
+        }
+    }
+}
+
+impl<T: Serialize> Encode for SerdeCodec<T> {
+    fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+        let bytes = to_bytes_nested(&self.0)?;
+        dest.write(bytes.as_slice());
+        Ok(())
+    }
+
+    fn top_encode(&self) -> Result<Vec<u8>, EncodeError> {
+        Ok(to_bytes(&self.0)?)
+    }
+
+    fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
+        let bytes = to_bytes(&self.0)?;
+        f(bytes.as_slice());
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> Decode for SerdeCodec<T> {
+    fn top_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+        let bytes = input.flush()?;
+        Ok(SerdeCodec(from_bytes(bytes)?))
+    }
+
+    /// The length of a nested serde value is only known after parsing it,
+    /// so the input is inspected first and only then advanced.
+    fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+        let (value, len) = match input.peek_remaining() {
+            Some(bytes) => from_bytes_nested(bytes)?,
+            None => return Err(DecodeError::Static(INPUT_NOT_PEEKABLE)),
+        };
+        input.read_slice(len)?;
+        Ok(SerdeCodec(value))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use core::fmt::Debug;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+    struct Profile {
+        name: String,
+        level: u16,
+    }
+
+    /// Same layout as `Profile`, with the numbat-codec traits implemented by hand.
+    #[derive(PartialEq, Debug)]
+    struct CodecProfile {
+        name: Vec<u8>,
+        level: u16,
+    }
+
+    impl Encode for CodecProfile {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+            self.name.dep_encode_to(dest)?;
+            self.level.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for CodecProfile {
+        fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+            Ok(CodecProfile {
+                name: Vec::<u8>::dep_decode(input)?,
+                level: u16::dep_decode(input)?,
+            })
+        }
+    }
+
+    fn profile(name: &str, level: u16) -> SerdeCodec<Profile> {
+        SerdeCodec(Profile { name: String::from(name), level })
+    }
+
+    fn codec_ok<V>(element: V, expected_bytes: &[u8])
+    where
+        V: Encode + Decode + PartialEq + Debug,
+    {
+        assert_eq!(element.top_encode().unwrap().as_slice(), expected_bytes);
+        let decoded: V = decode_from_byte_slice(expected_bytes).unwrap();
+        assert_eq!(decoded, element);
+    }
+
+    #[test]
+    fn test_top() {
+        codec_ok(profile("ab", 3), &[0, 0, 0, 2, b'a', b'b', 0, 3]);
+        codec_ok(SerdeCodec(5u32), &[5]);
+        codec_ok(SerdeCodec(Option::<u8>::None), &[]);
+    }
+
+    #[test]
+    fn test_nested() {
+        codec_ok(
+            alloc::vec![profile("a", 1), profile("", 2)],
+            &[0, 0, 0, 1, b'a', 0, 1, 0, 0, 0, 0, 0, 2]);
+        codec_ok(
+            (SerdeCodec(5u32), 6u8, Some(profile("", 1))),
+            &[0, 0, 0, 5, 6, 1, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode_from_byte_slice::<SerdeCodec<u8>>(&[1, 2]), Err(DecodeError::InputTooLong));
+        assert_eq!(
+            decode_from_byte_slice::<Vec<SerdeCodec<Profile>>>(&[0, 0, 0, 3, b'a']).unwrap_err().message_bytes(),
+            &b"input too short"[..]);
+        assert_eq!(decode_from_byte_slice::<SerdeCodec<String>>(&[0xff]), Err(DecodeError::InvalidValue));
+    }
+
+    #[test]
+    fn test_endpoint_arg() {
+        use numbat_wasm::*;
+
+        struct PanickingErrHandler;
+
+        impl DynArgErrHandler for PanickingErrHandler {
+            fn handle_sc_error(&self, _err: SCError) -> ! {
+                panic!("argument should have been loaded")
+            }
+        }
+
+        let input: &[u8] = b"func@0000000261620003@05";
+        let mut cd_loader = CallDataArgLoader::new(CallDataDeserializer::new(input));
+        let arg1: SerdeCodec<Profile> = load_dyn_arg(&mut cd_loader, &PanickingErrHandler, &b"profile"[..]);
+        assert_eq!(arg1, profile("ab", 3));
+        let arg2: SerdeCodec<u64> = load_dyn_arg(&mut cd_loader, &PanickingErrHandler, &b"amount"[..]);
+        assert_eq!(arg2.into_inner(), 5);
+    }
+
+    #[test]
+    fn test_storage() {
+        use numbat_wasm_debug::*;
+
+        let address = numbat_wasm::Address::from([1u8; 32]);
+        let mock_ref = AndesMockState::new_ref_with_account(&address);
+
+        numbat_wasm::storage_set(&mock_ref, &b"profile"[..], &profile("ab", 3));
+        let loaded: SerdeCodec<Profile> = numbat_wasm::storage_get(&mock_ref, &b"profile"[..]);
+        assert_eq!(loaded, profile("ab", 3));
+
+        let raw: Vec<u8> = numbat_wasm::storage_get(&mock_ref, &b"profile"[..]);
+        assert_eq!(raw, [0, 0, 0, 2, b'a', b'b', 0, 3]);
+
+        // serde types can be read back as the equivalent codec types
+        let codec_profile: CodecProfile = numbat_wasm::storage_get(&mock_ref, &b"profile"[..]);
+        assert_eq!(codec_profile, CodecProfile { name: b"ab".to_vec(), level: 3 });
+    }
+}
@@ -0,0 +1,161 @@
+//! Differential tests, checking that every fixture is encoded and decoded
+//! exactly the same by numbat-wasm-serde and numbat-codec.
+//!
+//! `usize`/`isize` fields use the `usize32`/`isize32` helpers, see the crate documentation.
+
+use super::*;
+use super::test_struct::*;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use numbat_codec::*;
+use serde::Serialize;
+
+fn parity_ok<V>(element: V, expected_bytes: &[u8])
+where
+    V: Serialize + serde::de::DeserializeOwned + Encode + Decode + PartialEq + Debug,
+{
+    let codec_bytes = element.top_encode().unwrap();
+    assert_eq!(codec_bytes.as_slice(), expected_bytes, "numbat-codec encoding mismatch");
+    let serde_bytes = to_bytes(&element).unwrap();
+    assert_eq!(serde_bytes.as_slice(), expected_bytes, "numbat-wasm-serde encoding mismatch");
+
+    let codec_decoded: V = decode_from_byte_slice(expected_bytes).unwrap();
+    assert_eq!(codec_decoded, element, "numbat-codec decoding mismatch");
+    let serde_decoded: V = from_bytes(expected_bytes).unwrap();
+    assert_eq!(serde_decoded, element, "numbat-wasm-serde decoding mismatch");
+}
+
+/// Both crates must reject the input.
+fn parity_err<V>(bytes: &[u8])
+where
+    V: serde::de::DeserializeOwned + Decode + Debug,
+{
+    assert!(decode_from_byte_slice::<V>(bytes).is_err(), "numbat-codec should reject {:?}", bytes);
+    assert!(from_bytes::<V>(bytes).is_err(), "numbat-wasm-serde should reject {:?}", bytes);
+}
+
+#[test]
+fn test_numbers() {
+    parity_ok(0u8, &[]);
+    parity_ok(0u64, &[]);
+    parity_ok(5u16, &[5]);
+    parity_ok(0x1234u32, &[0x12, 0x34]);
+    parity_ok(u64::MAX, &[0xff; 8]);
+    parity_ok(-5i8, &[251]);
+    parity_ok(-300i32, &[0xfe, 0xd4]);
+    parity_ok(i64::MIN, &[0x80, 0, 0, 0, 0, 0, 0, 0]);
+    parity_ok(0x1234i16, &[0x12, 0x34]);
+}
+
+#[test]
+fn test_numbers_invalid() {
+    parity_err::<u8>(&[1, 2]);
+    parity_err::<u16>(&[1, 2, 3]);
+    parity_err::<i32>(&[1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_bool_and_unit() {
+    parity_ok(true, &[1]);
+    parity_ok(false, &[]);
+    parity_ok((), &[]);
+    parity_ok((true, false), &[1, 0]);
+    parity_err::<bool>(&[2]);
+    parity_err::<bool>(&[1, 1]);
+    parity_err::<(bool, bool)>(&[1, 2]);
+}
+
+#[test]
+fn test_vecs() {
+    parity_ok(Vec::<u8>::new(), &[]);
+    parity_ok([1u8, 2, 3].to_vec(), &[1, 2, 3]);
+    parity_ok([1i32, -2].to_vec(), &[0, 0, 0, 1, 255, 255, 255, 254]);
+    parity_ok([[1u8].to_vec(), Vec::new()].to_vec(), &[0, 0, 0, 1, 1, 0, 0, 0, 0]);
+    parity_ok([Some(1u16), None].to_vec(), &[1, 0, 1, 0]);
+    parity_err::<Vec<u32>>(&[0, 0, 0, 1, 0, 0]);
+}
+
+#[test]
+fn test_options() {
+    parity_ok(Option::<u32>::None, &[]);
+    parity_ok(Some(0u8), &[1, 0]);
+    parity_ok(Some(5u32), &[1, 0, 0, 0, 5]);
+    parity_ok(Some([1i32].to_vec()), &[1, 0, 0, 0, 1, 0, 0, 0, 1]);
+    parity_ok(Some(Option::<u8>::None), &[1, 0]);
+    parity_ok(Some(Some(3u8)), &[1, 1, 3]);
+    parity_ok((Option::<u8>::None, 1u8), &[0, 1]);
+    parity_err::<Option<u8>>(&[2, 1]);
+    parity_err::<Option<u8>>(&[1, 1, 1]);
+}
+
+#[test]
+fn test_tuples_and_arrays() {
+    parity_ok((7u32, -2i16), &[0, 0, 0, 7, 255, 254]);
+    parity_ok((1u8, [2u8].to_vec(), true), &[1, 0, 0, 0, 1, 2, 1]);
+    parity_ok([1u8, 2, 3, 4, 5], &[1, 2, 3, 4, 5]);
+    parity_ok([1u16, 2, 3], &[0, 1, 0, 2, 0, 3]);
+    parity_ok([[1u8, 2], [3, 4]].to_vec(), &[1, 2, 3, 4]);
+    parity_ok(Some([7u8; 2]), &[1, 7, 7]);
+}
+
+#[test]
+fn test_structs() {
+    let test = Test {
+        int: 1,
+        seq: [5, 6].to_vec(),
+        another_byte: 7,
+    };
+    parity_ok(test, &[0, 1, 0, 0, 0, 2, 5, 6, 7]);
+    parity_ok(Some(Test { int: 0, seq: Vec::new(), another_byte: 0 }), &[1, 0, 0, 0, 0, 0, 0, 0]);
+    parity_ok(Newtype(5), &[0, 0, 0, 5]);
+    parity_ok(alloc::vec![Newtype(1), Newtype(2)], &[0, 0, 0, 1, 0, 0, 0, 2]);
+    parity_ok(Pair(1, -1), &[0, 1, 255]);
+    parity_ok(alloc::vec![Pair(1, 2), Pair(3, 4)], &[0, 1, 2, 0, 3, 4]);
+    parity_err::<Test>(&[0, 1, 0, 0, 0, 3, 5, 6, 7]);
+}
+
+#[test]
+fn test_sizes() {
+    parity_ok(Sizes { len: 5, offset: -1, tail: 7 }, &[0, 0, 0, 5, 255, 255, 255, 255, 7]);
+    parity_ok(alloc::vec![Sizes { len: 0, offset: 2, tail: 0 }], &[0, 0, 0, 0, 0, 0, 0, 2, 0]);
+    parity_err::<Sizes>(&[0, 0, 0, 5, 255, 255, 255, 255]);
+
+    // truncating would silently change the value
+    #[cfg(target_pointer_width = "64")]
+    assert!(to_bytes(&Sizes { len: 1 << 32, offset: 0, tail: 0 }).is_err());
+    #[cfg(target_pointer_width = "64")]
+    assert!(to_bytes(&Sizes { len: 0, offset: -(1 << 31) - 1, tail: 0 }).is_err());
+}
+
+#[test]
+fn test_enums() {
+    parity_ok(E::Unit, &[0, 0, 0, 0]);
+    parity_ok(E::Newtype(1), &[0, 0, 0, 1, 0, 0, 0, 1]);
+    parity_ok(E::Tuple(1, 2), &[0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2]);
+    parity_ok(E::Struct { a: 1 }, &[0, 0, 0, 3, 0, 0, 0, 1]);
+    parity_ok(alloc::vec![E::Unit, E::Struct { a: 5 }], &[0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 5]);
+    parity_ok(Some(E::Unit), &[1, 0, 0, 0, 0]);
+}
+
+/// numbat-codec has no owned string or map types,
+/// but strings must match `&str` and maps must match the equivalent list of pairs.
+#[test]
+fn test_strings_and_maps() {
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
+
+    let s = String::from("abc");
+    assert_eq!(to_bytes(&s).unwrap(), "abc".top_encode().unwrap());
+    assert_eq!(to_bytes((&s, 1u8)).unwrap(), ("abc", 1u8).top_encode().unwrap());
+    assert_eq!(decode_borrowed_from_byte_slice::<&str>(b"abc").unwrap(), from_bytes::<String>(b"abc").unwrap());
+
+    let mut map = BTreeMap::new();
+    map.insert(1u8, [2u16].to_vec());
+    map.insert(3u8, Vec::new());
+    let pairs = [(1u8, [2u16].to_vec()), (3u8, Vec::new())].to_vec();
+    assert_eq!(to_bytes(&map).unwrap(), pairs.top_encode().unwrap());
+    assert_eq!(to_bytes(Some(&map)).unwrap(), Some(pairs.clone()).top_encode().unwrap());
+
+    let decoded: BTreeMap<u8, Vec<u16>> = from_bytes(pairs.top_encode().unwrap().as_slice()).unwrap();
+    assert_eq!(decoded, map);
+}
@@ -1,16 +1,165 @@
+//! Serde serializer and deserializer for the numbat-codec format.
+//!
+//! Serde types encode to the same bytes as equivalent numbat-codec types, with one exception:
+//! serde hands `usize` and `isize` over as 64-bit numbers, while numbat-codec encodes them on 32 bits.
+//! The serializer cannot tell them apart from `u64` and `i64`, so fields of these types need
+//! `#[serde(with = "numbat_wasm_serde::usize32")]` or `#[serde(with = "numbat_wasm_serde::isize32")]`
+//! to be read back by numbat-codec types.
+
 extern crate alloc;
 
 mod bytes_ser;
 mod bytes_de;
 mod bytes_err;
+mod codec_adapter;
+mod size_32;
 
 pub use bytes_ser::{to_bytes, u64_to_bytes};
 pub use bytes_de::{from_bytes, bytes_to_number};
 pub use bytes_err::SDError;
+pub use codec_adapter::SerdeCodec;
+pub use size_32::{usize32, isize32};
+
+#[cfg(test)]
+mod codec_parity_tests;
+
+/// Fixtures shared by the serializer, deserializer and parity tests.
+/// They also implement the numbat-codec traits, by hand, to compare both encodings.
+#[cfg(test)]
+pub mod test_struct {
+    use alloc::vec::Vec;
+    use numbat_codec::*;
+    use serde::{Serialize, Deserialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    pub struct Test {
+        pub int: u16,
+        pub seq: Vec<u8>,
+        pub another_byte: u8,
+    }
+
+    impl Encode for Test {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> core::result::Result<(), EncodeError> {
+            self.int.dep_encode_to(dest)?;
+            self.seq.dep_encode_to(dest)?;
+            self.another_byte.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for Test {
+        fn dep_decode<I: Input>(input: &mut I) -> core::result::Result<Self, DecodeError> {
+            Ok(Test {
+                int: u16::dep_decode(input)?,
+                seq: Vec::<u8>::dep_decode(input)?,
+                another_byte: u8::dep_decode(input)?,
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+    pub enum E {
+        Unit,
+        Newtype(u32),
+        Tuple(u32, u32),
+        Struct { a: u32 },
+    }
+
+    impl Encode for E {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> core::result::Result<(), EncodeError> {
+            match self {
+                E::Unit => 0u32.dep_encode_to(dest),
+                E::Newtype(arg1) => {
+                    1u32.dep_encode_to(dest)?;
+                    arg1.dep_encode_to(dest)
+                },
+                E::Tuple(arg1, arg2) => {
+                    2u32.dep_encode_to(dest)?;
+                    arg1.dep_encode_to(dest)?;
+                    arg2.dep_encode_to(dest)
+                },
+                E::Struct { a } => {
+                    3u32.dep_encode_to(dest)?;
+                    a.dep_encode_to(dest)
+                },
+            }
+        }
+    }
+
+    impl Decode for E {
+        fn dep_decode<I: Input>(input: &mut I) -> core::result::Result<Self, DecodeError> {
+            match u32::dep_decode(input)? {
+                0 => Ok(E::Unit),
+                1 => Ok(E::Newtype(u32::dep_decode(input)?)),
+                2 => Ok(E::Tuple(u32::dep_decode(input)?, u32::dep_decode(input)?)),
+                3 => Ok(E::Struct { a: u32::dep_decode(input)? }),
+                _ => Err(DecodeError::InvalidValue),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    pub struct Newtype(pub u32);
+
+    impl Encode for Newtype {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> core::result::Result<(), EncodeError> {
+            self.0.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for Newtype {
+        fn dep_decode<I: Input>(input: &mut I) -> core::result::Result<Self, DecodeError> {
+            Ok(Newtype(u32::dep_decode(input)?))
+        }
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    pub struct Sizes {
+        #[serde(with = "crate::usize32")]
+        pub len: usize,
+        #[serde(with = "crate::isize32")]
+        pub offset: isize,
+        pub tail: u8,
+    }
+
+    impl Encode for Sizes {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> core::result::Result<(), EncodeError> {
+            self.len.dep_encode_to(dest)?;
+            self.offset.dep_encode_to(dest)?;
+            self.tail.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for Sizes {
+        fn dep_decode<I: Input>(input: &mut I) -> core::result::Result<Self, DecodeError> {
+            Ok(Sizes {
+                len: usize::dep_decode(input)?,
+                offset: isize::dep_decode(input)?,
+                tail: u8::dep_decode(input)?,
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    pub struct Pair(pub u16, pub i8);
+
+    impl Encode for Pair {
+        fn dep_encode_to<O: Output>(&self, dest: &mut O) -> core::result::Result<(), EncodeError> {
+            self.0.dep_encode_to(dest)?;
+            self.1.dep_encode_to(dest)
+        }
+    }
+
+    impl Decode for Pair {
+        fn dep_decode<I: Input>(input: &mut I) -> core::result::Result<Self, DecodeError> {
+            Ok(Pair(u16::dep_decode(input)?, i8::dep_decode(input)?))
+        }
+    }
+}
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use super::test_struct::*;
     use core::fmt::Debug;
     use alloc::vec::Vec;
     use serde::{Serialize, Deserialize};
@@ -91,18 +240,11 @@ pub mod tests {
         ser_deser_ok(some_v, expected);
 
         let none_v: Option<Vec<i32>> = None;
-        ser_deser_ok(none_v, &[0]);
+        ser_deser_ok(none_v, &[]);
     }
 
     #[test]
     fn test_struct() {
-        #[derive(Serialize, Deserialize, PartialEq, Debug)]
-        struct Test {
-            int: u16,
-            seq: Vec<u8>,
-            another_byte: u8,
-        }
-
         let test = Test {
             int: 1,
             seq: [5, 6].to_vec(),
@@ -111,6 +253,32 @@ pub mod tests {
         the_same(test);
     }
 
+    #[test]
+    fn test_struct_with_string_and_map() {
+        use alloc::collections::BTreeMap;
+        use alloc::string::String;
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Profile {
+            name: String,
+            scores: BTreeMap<String, u32>,
+            nickname: Option<String>,
+        }
+
+        let mut scores = BTreeMap::new();
+        scores.insert(String::from("a"), 1);
+        let profile = Profile {
+            name: String::from("xy"),
+            scores,
+            nickname: None,
+        };
+        let expected: &[u8] = &[
+            /*name*/ 0, 0, 0, 2, b'x', b'y',
+            /*scores*/ 0, 0, 0, 1, 0, 0, 0, 1, b'a', 0, 0, 0, 1,
+            /*nickname*/ 0];
+        ser_deser_ok(profile, expected);
+    }
+
     #[test]
     fn test_wrapped_array() {
         #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
@@ -0,0 +1,40 @@
+use core::convert::TryFrom;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+/// Serializes a `usize` field on 32 bits, the way numbat-codec encodes it:
+/// `#[serde(with = "numbat_wasm_serde::usize32")]`.
+///
+/// Values that do not fit in a `u32` are rejected instead of being truncated.
+pub mod usize32 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &usize, serializer: S) -> Result<S::Ok, S::Error> {
+        match u32::try_from(*value) {
+            Ok(value) => value.serialize(serializer),
+            Err(_) => Err(serde::ser::Error::custom("usize value does not fit in 32 bits")),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+        u32::deserialize(deserializer).map(|value| value as usize)
+    }
+}
+
+/// Serializes an `isize` field on 32 bits, the way numbat-codec encodes it:
+/// `#[serde(with = "numbat_wasm_serde::isize32")]`.
+///
+/// Values that do not fit in an `i32` are rejected instead of being truncated.
+pub mod isize32 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &isize, serializer: S) -> Result<S::Ok, S::Error> {
+        match i32::try_from(*value) {
+            Ok(value) => value.serialize(serializer),
+            Err(_) => Err(serde::ser::Error::custom("isize value does not fit in 32 bits")),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<isize, D::Error> {
+        i32::deserialize(deserializer).map(|value| value as isize)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::*;
+use crate::call_data::CallDataSerializer;
+use numbat_codec::Encode;
+
+/// Prefix of the storage keys under which callback closures wait for their async call to return.
+pub const CALLBACK_CLOSURE_KEY_PREFIX: &[u8] = b"callback_closure";
+
+/// Storage key of the callback closure of an async call.
+/// Each async call of a transaction gets its own key, so several of them can be pending at once.
+pub fn callback_closure_key(tx_hash: &H256, call_index: u32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(CALLBACK_CLOSURE_KEY_PREFIX.len() + 32 + 4);
+    key.extend_from_slice(CALLBACK_CLOSURE_KEY_PREFIX);
+    key.extend_from_slice(tx_hash.as_bytes());
+    key.extend_from_slice(&call_index.to_be_bytes()[..]);
+    key
+}
+
+/// Loads the closure of the callback currently executing.
+/// Empty if the async call was made without a callback.
+pub fn load_callback_closure<A, BigInt, BigUint>(api: &A) -> Vec<u8>
+where
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint>,
+{
+    api.storage_load(callback_closure_key(&api.get_tx_hash(), api.get_callback_call_index()).as_slice())
+}
+
+/// Removes the closure of the callback currently executing, once it is no longer needed.
+pub fn clear_callback_closure<A, BigInt, BigUint>(api: &A)
+where
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint>,
+{
+    api.storage_store(callback_closure_key(&api.get_tx_hash(), api.get_callback_call_index()).as_slice(), &[]);
+}
+
+/// The callback to run when an async call returns, together with the arguments it needs from the original call.
+///
+/// The arguments are kept in storage until the callback runs and are passed to it before the async call results.
+pub struct CallbackClosure(CallDataSerializer);
+
+impl CallbackClosure {
+    pub fn new(callback_name: &[u8]) -> Self {
+        CallbackClosure(CallDataSerializer::new(callback_name))
+    }
+
+    #[inline]
+    pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
+        self.0.push_argument_bytes(bytes);
+    }
+
+    #[inline]
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        self.0.push_arg(arg)
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl From<CallDataSerializer> for CallbackClosure {
+    #[inline]
+    fn from(serializer: CallDataSerializer) -> Self {
+        CallbackClosure(serializer)
+    }
+}
+
+/// Builds an async call to another contract.
+///
+/// Value, gas limit and callback are all optional.
+/// On Andes, execution stops at the first async call, so a transaction can only make one,
+/// and gas limits are not supported. The debugger mock supports both, for testing.
+/// Example:
+/// `AsyncCall::new(&address, b"deposit").with_value(amount).with_callback(closure).execute(&self.api)`.
+pub struct AsyncCall<BigUint> {
+    to: Address,
+    value: Option<BigUint>,
+    gas_limit: Option<u64>,
+    call_data: CallDataSerializer,
+    callback: Option<CallbackClosure>,
+}
+
+impl<BigUint> AsyncCall<BigUint>
+where
+    BigUint: BigUintApi + 'static,
+{
+    pub fn new(to: &Address, endpoint_name: &[u8]) -> Self {
+        AsyncCall::from_call_data(to, CallDataSerializer::new(endpoint_name))
+    }
+
+    /// Async call with the endpoint name and arguments already serialized.
+    pub fn from_call_data(to: &Address, call_data: CallDataSerializer) -> Self {
+        AsyncCall {
+            to: to.clone(),
+            value: None,
+            gas_limit: None,
+            call_data,
+            callback: None,
+        }
+    }
+
+    pub fn with_value(mut self, value: BigUint) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_callback(mut self, callback: CallbackClosure) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    #[inline]
+    pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
+        self.call_data.push_argument_bytes(bytes);
+    }
+
+    /// Top-encodes an endpoint argument and appends it to the call data.
+    #[inline]
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        self.call_data.push_arg(arg)
+    }
+
+    #[inline]
+    pub fn call_data(&self) -> &[u8] {
+        self.call_data.as_slice()
+    }
+
+    /// Saves the callback closure, if any, then performs the call.
+    pub fn execute<A, BigInt>(self, api: &A)
+    where
+        BigInt: Encode + 'static,
+        A: ContractHookApi<BigInt, BigUint>,
+    {
+        if let Some(callback) = &self.callback {
+            let key = callback_closure_key(&api.get_tx_hash(), api.get_num_async_calls());
+            api.storage_store(key.as_slice(), callback.as_slice());
+        }
+        let value = self.value.unwrap_or_else(BigUint::zero);
+        match self.gas_limit {
+            Some(gas_limit) => api.async_call_with_gas(&self.to, &value, self.call_data.as_slice(), gas_limit),
+            None => api.async_call(&self.to, &value, self.call_data.as_slice()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_closure_key() {
+        let key = callback_closure_key(&H256::from([1u8; 32]), 0x0102);
+        assert_eq!(&key[..CALLBACK_CLOSURE_KEY_PREFIX.len()], CALLBACK_CLOSURE_KEY_PREFIX);
+        assert_eq!(&key[CALLBACK_CLOSURE_KEY_PREFIX.len()..CALLBACK_CLOSURE_KEY_PREFIX.len() + 32], &[1u8; 32][..]);
+        assert_eq!(&key[CALLBACK_CLOSURE_KEY_PREFIX.len() + 32..], &[0, 0, 1, 2][..]);
+        assert_ne!(key, callback_closure_key(&H256::from([1u8; 32]), 0x0103));
+    }
+
+    #[test]
+    fn test_callback_closure() {
+        let mut closure = CallbackClosure::new(&b"cb"[..]);
+        closure.push_arg(&5u32).unwrap();
+        closure.push_argument_bytes(&[]);
+        assert_eq!(closure.as_slice(), &b"cb@05@"[..]);
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use numbat_codec::Encode;
+use crate::err_msg;
+use crate::io::sc_error::SCError;
+
+use super::cd_ser::CallDataSerializer;
+use super::cd_de::CallDataDeserializer;
+use super::cd_value::CallData;
+
+pub const DCT_TRANSFER_FUNC_NAME: &[u8] = b"DCTTransfer";
+pub const SAVE_KEY_VALUE_FUNC_NAME: &[u8] = b"SaveKeyValue";
+pub const SET_USERNAME_FUNC_NAME: &[u8] = b"SetUserName";
+pub const CLAIM_DEVELOPER_REWARDS_FUNC_NAME: &[u8] = b"ClaimDeveloperRewards";
+
+/// Call to one of the protocol built-in functions.
+///
+/// Build one with the constructors, then send `serialize()` as call data, e.g. via `async_call`.
+/// `parse` does the reverse, recognizing built-in function calls in arbitrary call data.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BuiltinFunctionCall {
+    /// Transfers fungible tokens to the destination.
+    /// If the destination is a contract, one of its functions can also be called in the same transaction.
+    DctTransfer {
+        token_name: Vec<u8>,
+        /// Top-encoded amount.
+        amount: Vec<u8>,
+        then_call: Option<CallData>,
+    },
+    /// Writes key-value pairs in the storage of the sender.
+    SaveKeyValue(Vec<(Vec<u8>, Vec<u8>)>),
+    SetUsername(Vec<u8>),
+    ClaimDeveloperRewards,
+}
+
+impl BuiltinFunctionCall {
+    pub fn dct_transfer<BigUint: Encode>(token_name: &[u8], amount: &BigUint) -> Result<Self, SCError> {
+        Ok(BuiltinFunctionCall::DctTransfer {
+            token_name: token_name.to_vec(),
+            amount: amount.top_encode().map_err(SCError::PushAsyncEncodeErr)?,
+            then_call: None,
+        })
+    }
+
+    /// Transfers tokens and calls a function of the receiving contract.
+    pub fn dct_transfer_and_call<BigUint: Encode>(token_name: &[u8], amount: &BigUint, call: CallData) -> Result<Self, SCError> {
+        Ok(BuiltinFunctionCall::DctTransfer {
+            token_name: token_name.to_vec(),
+            amount: amount.top_encode().map_err(SCError::PushAsyncEncodeErr)?,
+            then_call: Some(call),
+        })
+    }
+
+    pub fn save_key_value(pairs: &[(&[u8], &[u8])]) -> Self {
+        BuiltinFunctionCall::SaveKeyValue(
+            pairs.iter().map(|(key, value)| (key.to_vec(), value.to_vec())).collect())
+    }
+
+    pub fn set_username(username: &[u8]) -> Self {
+        BuiltinFunctionCall::SetUsername(username.to_vec())
+    }
+
+    pub fn claim_developer_rewards() -> Self {
+        BuiltinFunctionCall::ClaimDeveloperRewards
+    }
+
+    pub fn func_name(&self) -> &'static [u8] {
+        match self {
+            BuiltinFunctionCall::DctTransfer { .. } => DCT_TRANSFER_FUNC_NAME,
+            BuiltinFunctionCall::SaveKeyValue(_) => SAVE_KEY_VALUE_FUNC_NAME,
+            BuiltinFunctionCall::SetUsername(_) => SET_USERNAME_FUNC_NAME,
+            BuiltinFunctionCall::ClaimDeveloperRewards => CLAIM_DEVELOPER_REWARDS_FUNC_NAME,
+        }
+    }
+
+    /// Produces the call data.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut ser = CallDataSerializer::new(self.func_name());
+        match self {
+            BuiltinFunctionCall::DctTransfer { token_name, amount, then_call } => {
+                ser.push_argument_bytes(token_name.as_slice());
+                ser.push_argument_bytes(amount.as_slice());
+                if let Some(call) = then_call {
+                    ser.push_argument_bytes(call.func_name());
+                    for arg in call.args().iter() {
+                        ser.push_argument_bytes(arg.as_slice());
+                    }
+                }
+            },
+            BuiltinFunctionCall::SaveKeyValue(pairs) => {
+                for (key, value) in pairs.iter() {
+                    ser.push_argument_bytes(key.as_slice());
+                    ser.push_argument_bytes(value.as_slice());
+                }
+            },
+            BuiltinFunctionCall::SetUsername(username) => {
+                ser.push_argument_bytes(username.as_slice());
+            },
+            BuiltinFunctionCall::ClaimDeveloperRewards => {},
+        }
+        ser.into_vec()
+    }
+
+    /// Recognizes built-in function calls.
+    /// Returns `None` if the call data calls some other function.
+    pub fn parse(source: &[u8]) -> Result<Option<Self>, SCError> {
+        let mut de = CallDataDeserializer::new(source);
+        let func_name = de.get_func_name();
+        let call = if func_name == DCT_TRANSFER_FUNC_NAME {
+            let token_name = next_builtin_argument(&mut de)?;
+            let amount = next_builtin_argument(&mut de)?;
+            let then_call = match de.next_argument()? {
+                None => None,
+                Some(then_func_name) => {
+                    let mut call = CallData::new(then_func_name.as_slice());
+                    while let Some(arg) = de.next_argument()? {
+                        call.push_argument_bytes(arg.as_slice());
+                    }
+                    Some(call)
+                },
+            };
+            BuiltinFunctionCall::DctTransfer { token_name, amount, then_call }
+        } else if func_name == SAVE_KEY_VALUE_FUNC_NAME {
+            let mut pairs = Vec::new();
+            while let Some(key) = de.next_argument()? {
+                pairs.push((key, next_builtin_argument(&mut de)?));
+            }
+            BuiltinFunctionCall::SaveKeyValue(pairs)
+        } else if func_name == SET_USERNAME_FUNC_NAME {
+            BuiltinFunctionCall::SetUsername(next_builtin_argument(&mut de)?)
+        } else if func_name == CLAIM_DEVELOPER_REWARDS_FUNC_NAME {
+            BuiltinFunctionCall::ClaimDeveloperRewards
+        } else {
+            return Ok(None);
+        };
+
+        if de.has_next() && !matches!(call, BuiltinFunctionCall::DctTransfer { .. }) {
+            return Err(SCError::Static(err_msg::BUILTIN_WRONG_ARGS));
+        }
+        Ok(Some(call))
+    }
+}
+
+fn next_builtin_argument(de: &mut CallDataDeserializer) -> Result<Vec<u8>, SCError> {
+    match de.next_argument()? {
+        Some(arg) => Ok(arg),
+        None => Err(SCError::Static(err_msg::BUILTIN_WRONG_ARGS)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(call: BuiltinFunctionCall, expected: &[u8]) {
+        let serialized = call.serialize();
+        assert_eq!(serialized.as_slice(), expected);
+        assert_eq!(BuiltinFunctionCall::parse(serialized.as_slice()), Ok(Some(call)));
+    }
+
+    #[test]
+    fn test_dct_transfer() {
+        round_trip(
+            BuiltinFunctionCall::dct_transfer(&b"TKN"[..], &1000u32).unwrap(),
+            &b"DCTTransfer@544b4e@03e8"[..]);
+    }
+
+    #[test]
+    fn test_dct_transfer_and_call() {
+        let mut call = CallData::new(&b"deposit"[..]);
+        call.push_arg(&5u8).unwrap();
+        round_trip(
+            BuiltinFunctionCall::dct_transfer_and_call(&b"TKN"[..], &1u32, call).unwrap(),
+            &b"DCTTransfer@544b4e@01@6465706f736974@05"[..]);
+    }
+
+    #[test]
+    fn test_save_key_value() {
+        round_trip(
+            BuiltinFunctionCall::save_key_value(&[(&b"ab"[..], &[1u8][..]), (&[0xffu8][..], &[][..])]),
+            &b"SaveKeyValue@6162@01@ff@"[..]);
+        assert_eq!(BuiltinFunctionCall::parse(&b"SaveKeyValue@6162"[..]),
+            Err(SCError::Static(err_msg::BUILTIN_WRONG_ARGS)));
+    }
+
+    #[test]
+    fn test_set_username_and_claim() {
+        round_trip(BuiltinFunctionCall::set_username(&b"alice"[..]), &b"SetUserName@616c696365"[..]);
+        round_trip(BuiltinFunctionCall::claim_developer_rewards(), &b"ClaimDeveloperRewards"[..]);
+        assert_eq!(BuiltinFunctionCall::parse(&b"SetUserName"[..]),
+            Err(SCError::Static(err_msg::BUILTIN_WRONG_ARGS)));
+        assert_eq!(BuiltinFunctionCall::parse(&b"ClaimDeveloperRewards@01"[..]),
+            Err(SCError::Static(err_msg::BUILTIN_WRONG_ARGS)));
+    }
+
+    #[test]
+    fn test_not_builtin() {
+        assert_eq!(BuiltinFunctionCall::parse(&b"deposit@05"[..]), Ok(None));
+    }
+}
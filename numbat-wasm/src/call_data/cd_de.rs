@@ -1,4 +1,5 @@
 use alloc::vec::Vec;
+use numbat_codec::{Decode, append_decimal};
 use crate::err_msg;
 use crate::io::sc_error::*;
 use super::SEPARATOR;
@@ -37,14 +38,21 @@ fn hex_to_byte(digit1: u8, digit2: u8) -> Option<u8> {
 /// Arguments can be empty.
 /// Argument hex encodings must always have an even number of digits.
 /// 
-/// CallDataDeserializer borrows its input and will allocate new Vecs for each output.
+/// CallDataDeserializer borrows its input and will allocate new Vecs for each output,
+/// unless the arguments are decoded into a caller-provided buffer via `next_argument_to_slice`.
 /// 
-/// Converting from bytes to specific argument types is not in scope. Use the `serializer` module for that.
+/// Arguments can be retrieved either as raw bytes, or decoded to any type via `next_arg`.
+/// 
+/// Malformed arguments produce the static error messages from `err_msg`.
+/// With the `call-data-error-position` feature, the messages also contain
+/// the index of the argument and the offset of the problem in the call data, at the cost of a larger binary.
 /// 
 pub struct CallDataDeserializer<'a> {
     source: &'a [u8],
     index: usize,
     func_name_output: &'a [u8],
+    /// Arguments retrieved so far, not counting the function name.
+    arg_count: usize,
 }
 
 impl<'a> CallDataDeserializer<'a> {
@@ -53,11 +61,13 @@ impl<'a> CallDataDeserializer<'a> {
             source,
             index: 0,
             func_name_output: &[],
+            arg_count: 0,
         };
 
         // extract func name and advance index, before any argument can be retrieved
         if let Some(func_name) = de.next_argument_hex() {
-            de.func_name_output = func_name
+            de.func_name_output = func_name;
+            de.arg_count = 0;
         }
 
         de
@@ -80,6 +90,7 @@ impl<'a> CallDataDeserializer<'a> {
             if self.index == self.source.len() {
                 let slice = &self.source[initial_index..self.index];
                 self.index += 1; // make index = len + 1 to signal that we are done, and return None from the next call on
+                self.arg_count += 1;
                 return Some(slice);
             }
             
@@ -87,6 +98,7 @@ impl<'a> CallDataDeserializer<'a> {
             if c == SEPARATOR {
                 let slice = &self.source[initial_index..self.index];
                 self.index += 1;
+                self.arg_count += 1;
                 return Some(slice);
             }
 
@@ -99,32 +111,183 @@ impl<'a> CallDataDeserializer<'a> {
         self.index <= self.source.len()
     }
 
+    /// Where the argument last returned by `next_argument_hex` starts.
+    fn last_arg_position(&self, arg_hex: &[u8]) -> CallDataPosition {
+        CallDataPosition::Argument {
+            arg_index: self.arg_count - 1,
+            // the index is always just past the separator or end of input that follows the argument
+            offset: self.index - arg_hex.len() - 1,
+        }
+    }
+
+    /// Length of the next argument once deserialized from hex, without retrieving it.
+    /// 0 if there are no more arguments.
+    pub fn next_argument_len(&self) -> usize {
+        if !self.has_next() {
+            return 0;
+        }
+        let rest = &self.source[self.index..];
+        let hex_len = rest.iter().position(|c| *c == SEPARATOR).unwrap_or(rest.len());
+        hex_len / 2
+    }
+
+    /// Gets the function name, deserialized from hex.
+    /// Only makes sense for formats where the first component is hex too, like the deploy format.
+    pub(super) fn func_name_from_hex(&self) -> Result<Vec<u8>, SCError> {
+        hex_to_vec(self.func_name_output)
+            .map_err(|hex_err| hex_err.into_sc_error(CallDataPosition::FuncName))
+    }
+
     /// Gets the next argument, deserializes from hex and returns the resulting bytes.
     pub fn next_argument(&mut self) -> Result<Option<Vec<u8>>, SCError> {
         match self.next_argument_hex() {
             None => Ok(None),
             Some(arg_hex) => {
+                match hex_to_vec(arg_hex) {
+                    Ok(arg) => Ok(Some(arg)),
+                    Err(hex_err) => Err(hex_err.into_sc_error(self.last_arg_position(arg_hex))),
+                }
+            }
+        }
+    }
+
+    /// Gets the next argument and top-decodes it.
+    pub fn next_arg<T: Decode>(&mut self) -> Result<Option<T>, SCError> {
+        match self.next_argument()? {
+            None => Ok(None),
+            Some(arg_bytes) => {
+                match numbat_codec::decode_from_byte_slice(arg_bytes.as_slice()) {
+                    Ok(v) => Ok(Some(v)),
+                    Err(de_err) => {
+                        let mut msg = Vec::new();
+                        msg.extend_from_slice(err_msg::DESERIALIZATION_DECODE_ERROR);
+                        de_err.append_message_to(&mut msg);
+                        Err(SCError::Dynamic(msg))
+                    },
+                }
+            }
+        }
+    }
+
+    /// Top-decodes all remaining arguments as the same type.
+    pub fn remaining_args<T: Decode>(&mut self) -> Result<Vec<T>, SCError> {
+        let mut result = Vec::new();
+        while let Some(arg) = self.next_arg()? {
+            result.push(arg);
+        }
+        Ok(result)
+    }
+
+    /// Gets the next argument and deserializes it from hex into the provided buffer,
+    /// instead of allocating a new Vec.
+    /// Returns the part of the buffer that was filled.
+    pub fn next_argument_to_slice<'b>(&mut self, dest: &'b mut [u8]) -> Result<Option<&'b [u8]>, SCError> {
+        match self.next_argument_hex() {
+            None => Ok(None),
+            Some(arg_hex) => {
+                let position = self.last_arg_position(arg_hex);
                 if arg_hex.len() % 2 != 0 {
-                    return Err(SCError::Static(err_msg::DESERIALIZATION_ODD_DIGITS));
+                    return Err(HexError::OddDigits.into_sc_error(position));
                 }
                 let res_len = arg_hex.len() / 2;
-                let mut res_vec = Vec::with_capacity(res_len);
-                for i in 0..res_len {
-                    match hex_to_byte(arg_hex[2*i], arg_hex[2*i+1]) {
-                        None => {
-                            return Err(SCError::Static(err_msg::DESERIALIZATION_INVALID_BYTE));
-                        },
-                        Some(byte) => {
-                            res_vec.push(byte);
-                        }
-                    }
+                if res_len > dest.len() {
+                    return Err(positioned_error(err_msg::DESERIALIZATION_BUFFER_TOO_SMALL, position));
                 }
-                Ok(Some(res_vec))
+                let res_slice = &mut dest[..res_len];
+                hex_to_slice(arg_hex, res_slice)
+                    .map_err(|hex_err| hex_err.into_sc_error(position))?;
+                Ok(Some(res_slice))
             }
         }
     }
 }
 
+/// Location of a malformed component in the call data.
+#[derive(Clone, Copy)]
+enum CallDataPosition {
+    FuncName,
+    Argument {
+        arg_index: usize,
+        /// Where the argument starts in the call data.
+        offset: usize,
+    },
+}
+
+/// Problem found while decoding hex digits.
+enum HexError {
+    OddDigits,
+    /// Holds the position of the invalid digit pair, relative to the start of the hex.
+    InvalidByte(usize),
+}
+
+impl HexError {
+    fn into_sc_error(self, position: CallDataPosition) -> SCError {
+        match self {
+            HexError::OddDigits => positioned_error(err_msg::DESERIALIZATION_ODD_DIGITS, position),
+            HexError::InvalidByte(hex_offset) => {
+                let position = match position {
+                    CallDataPosition::FuncName => CallDataPosition::FuncName,
+                    CallDataPosition::Argument { arg_index, offset } => CallDataPosition::Argument {
+                        arg_index,
+                        offset: offset + hex_offset,
+                    },
+                };
+                positioned_error(err_msg::DESERIALIZATION_INVALID_BYTE, position)
+            },
+        }
+    }
+}
+
+fn positioned_error(msg: &'static [u8], position: CallDataPosition) -> SCError {
+    if cfg!(feature = "call-data-error-position") {
+        SCError::Dynamic(positioned_message(msg, position))
+    } else {
+        SCError::Static(msg)
+    }
+}
+
+/// Appends the location to the message, e.g. "... (argument 1, offset 10)".
+fn positioned_message(msg: &[u8], position: CallDataPosition) -> Vec<u8> {
+    let mut result = Vec::with_capacity(msg.len() + 32);
+    result.extend_from_slice(msg);
+    match position {
+        CallDataPosition::FuncName => result.extend_from_slice(b" (function name)"),
+        CallDataPosition::Argument { arg_index, offset } => {
+            result.extend_from_slice(b" (argument ");
+            append_decimal(arg_index, &mut result);
+            result.extend_from_slice(b", offset ");
+            append_decimal(offset, &mut result);
+            result.push(b')');
+        },
+    }
+    result
+}
+
+/// Decodes hex digits into a new Vec.
+fn hex_to_vec(arg_hex: &[u8]) -> Result<Vec<u8>, HexError> {
+    if arg_hex.len() % 2 == 1 {
+        return Err(HexError::OddDigits);
+    }
+    let mut res_vec = alloc::vec![0u8; arg_hex.len() / 2];
+    hex_to_slice(arg_hex, res_vec.as_mut_slice())?;
+    Ok(res_vec)
+}
+
+/// Decodes hex digits into the destination, which must be exactly half the length of the input.
+fn hex_to_slice(arg_hex: &[u8], dest: &mut [u8]) -> Result<(), HexError> {
+    for (i, byte) in dest.iter_mut().enumerate() {
+        match hex_to_byte(arg_hex[2*i], arg_hex[2*i+1]) {
+            None => {
+                return Err(HexError::InvalidByte(2*i));
+            },
+            Some(b) => {
+                *byte = b;
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,11 +399,81 @@ mod tests {
         let input: &[u8] = b"func@123";
         let mut de = CallDataDeserializer::new(input);
         assert_eq!(de.get_func_name(), &b"func"[..]);
-        assert_eq!(de.next_argument(),  Err(SCError::Static(err_msg::DESERIALIZATION_ODD_DIGITS)));
+        assert_eq!(de.next_argument(),  Err(expected_error(err_msg::DESERIALIZATION_ODD_DIGITS, b" (argument 0, offset 5)")));
         assert_eq!(de.next_argument(), Ok(None));
         assert_eq!(de.next_argument(), Ok(None));
     }
 
+    #[test]
+    fn test_positioned_message() {
+        let position = CallDataPosition::Argument { arg_index: 12, offset: 305 };
+        assert_eq!(positioned_message(b"error", position).as_slice(), &b"error (argument 12, offset 305)"[..]);
+        let position = CallDataPosition::Argument { arg_index: 0, offset: 0 };
+        assert_eq!(positioned_message(b"error", position).as_slice(), &b"error (argument 0, offset 0)"[..]);
+        assert_eq!(positioned_message(b"error", CallDataPosition::FuncName).as_slice(), &b"error (function name)"[..]);
+    }
+
+    fn expected_error(msg: &'static [u8], position: &[u8]) -> SCError {
+        if cfg!(feature = "call-data-error-position") {
+            let mut full_msg = msg.to_vec();
+            full_msg.extend_from_slice(position);
+            SCError::Dynamic(full_msg)
+        } else {
+            SCError::Static(msg)
+        }
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let input: &[u8] = b"func@1111@12x4@123@@abcdef";
+        let mut de = CallDataDeserializer::new(input);
+        assert_eq!(de.next_argument(), Ok(Some([0x11, 0x11].to_vec())));
+        assert_eq!(de.next_argument(),
+            Err(expected_error(err_msg::DESERIALIZATION_INVALID_BYTE, b" (argument 1, offset 12)")));
+        assert_eq!(de.next_argument(),
+            Err(expected_error(err_msg::DESERIALIZATION_ODD_DIGITS, b" (argument 2, offset 15)")));
+        let mut buffer = [0u8; 2];
+        assert_eq!(de.next_argument_to_slice(&mut buffer), Ok(Some(&[][..])));
+        assert_eq!(de.next_argument_to_slice(&mut buffer),
+            Err(expected_error(err_msg::DESERIALIZATION_BUFFER_TOO_SMALL, b" (argument 4, offset 20)")));
+        assert_eq!(de.next_argument(), Ok(None));
+    }
+
+    #[test]
+    fn test_next_arg() {
+        let input: &[u8] = b"func@1234@@01000000010002@0102";
+        let mut de = CallDataDeserializer::new(input);
+        assert_eq!(de.next_arg::<u32>(), Ok(Some(0x1234)));
+        assert_eq!(de.next_arg::<u64>(), Ok(Some(0)));
+        assert_eq!(de.next_arg::<(u8, Vec<u16>)>(), Ok(Some((1, [2].to_vec()))));
+        assert_eq!(de.next_arg::<u8>(),
+            Err(SCError::Dynamic(b"call data deserialization error: input too long".to_vec())));
+        assert_eq!(de.next_arg::<u8>(), Ok(None));
+    }
+
+    #[test]
+    fn test_next_argument_to_slice() {
+        let input: &[u8] = b"func@1234@@abcdef";
+        let mut de = CallDataDeserializer::new(input);
+        let mut buffer = [0u8; 2];
+        assert_eq!(de.next_argument_to_slice(&mut buffer), Ok(Some(&[0x12, 0x34][..])));
+        assert_eq!(de.next_argument_to_slice(&mut buffer), Ok(Some(&[][..])));
+        assert_eq!(de.next_argument_to_slice(&mut buffer),
+            Err(expected_error(err_msg::DESERIALIZATION_BUFFER_TOO_SMALL, b" (argument 2, offset 11)")));
+        assert_eq!(de.next_argument_to_slice(&mut buffer), Ok(None));
+    }
+
+    #[test]
+    fn test_next_argument_to_slice_invalid() {
+        let input: &[u8] = b"func@12x4@123";
+        let mut de = CallDataDeserializer::new(input);
+        let mut buffer = [0u8; 4];
+        assert_eq!(de.next_argument_to_slice(&mut buffer),
+            Err(expected_error(err_msg::DESERIALIZATION_INVALID_BYTE, b" (argument 0, offset 7)")));
+        assert_eq!(de.next_argument_to_slice(&mut buffer),
+            Err(expected_error(err_msg::DESERIALIZATION_ODD_DIGITS, b" (argument 1, offset 10)")));
+    }
+
     // #[test]
     // fn test_next_u64_1() {
     //     let cd = CallDataSerializer::from_raw_data((&b"func@5").to_vec());
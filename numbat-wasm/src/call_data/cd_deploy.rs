@@ -0,0 +1,317 @@
+use alloc::vec::Vec;
+use core::ops::BitOr;
+use numbat_codec::Encode;
+use crate::err_msg;
+use crate::io::sc_error::SCError;
+
+use super::cd_ser::CallDataSerializer;
+use super::cd_de::CallDataDeserializer;
+
+/// VM type of contracts running on Andes, the Numbat WebAssembly VM.
+pub const VM_TYPE_ANDES: &[u8] = &[0x05, 0x00];
+
+/// Function name that marks contract upgrade transactions.
+pub const UPGRADE_FUNC_NAME: &[u8] = b"upgradeContract";
+
+/// Flags set on a contract when it is deployed or upgraded.
+/// Serialized as 2 bytes, big endian.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CodeMetadata(u16);
+
+impl CodeMetadata {
+    pub const DEFAULT: CodeMetadata = CodeMetadata(0);
+    pub const UPGRADEABLE: CodeMetadata = CodeMetadata(0x0100);
+    pub const READABLE: CodeMetadata = CodeMetadata(0x0400);
+    pub const PAYABLE: CodeMetadata = CodeMetadata(0x0002);
+
+    #[inline]
+    pub fn is_upgradeable(&self) -> bool {
+        self.0 & CodeMetadata::UPGRADEABLE.0 != 0
+    }
+
+    #[inline]
+    pub fn is_readable(&self) -> bool {
+        self.0 & CodeMetadata::READABLE.0 != 0
+    }
+
+    #[inline]
+    pub fn is_payable(&self) -> bool {
+        self.0 & CodeMetadata::PAYABLE.0 != 0
+    }
+
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SCError> {
+        if bytes.len() != 2 {
+            return Err(SCError::Static(err_msg::DESERIALIZATION_BAD_CODE_METADATA));
+        }
+        Ok(CodeMetadata(u16::from_be_bytes([bytes[0], bytes[1]])))
+    }
+}
+
+impl BitOr for CodeMetadata {
+    type Output = CodeMetadata;
+
+    #[inline]
+    fn bitor(self, other: CodeMetadata) -> CodeMetadata {
+        CodeMetadata(self.0 | other.0)
+    }
+}
+
+/// Serializes to Numbat's contract deploy format.
+///
+/// This format consists of the hex-encoded contract code, the VM type and the code metadata,
+/// followed by the init arguments, all separated by '@' characters.
+/// Example: "0061736d01000000@0500@0100@1234".
+pub struct DeploySerializer(CallDataSerializer);
+
+impl DeploySerializer {
+    pub fn new(code: &[u8], vm_type: &[u8], code_metadata: CodeMetadata) -> Self {
+        let mut ser = CallDataSerializer::new(&[]);
+        ser.push_hex(code);
+        ser.push_argument_bytes(vm_type);
+        ser.push_argument_bytes(&code_metadata.to_bytes()[..]);
+        DeploySerializer(ser)
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+
+    #[inline]
+    pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
+        self.0.push_argument_bytes(bytes);
+    }
+
+    #[inline]
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        self.0.push_arg(arg)
+    }
+}
+
+/// Serializes to Numbat's contract upgrade format.
+///
+/// This is a regular function call to `upgradeContract`, with the new code and the code metadata as first arguments,
+/// followed by the init arguments.
+/// Example: "upgradeContract@0061736d01000000@0100@1234".
+pub struct UpgradeSerializer(CallDataSerializer);
+
+impl UpgradeSerializer {
+    pub fn new(code: &[u8], code_metadata: CodeMetadata) -> Self {
+        let mut ser = CallDataSerializer::new(UPGRADE_FUNC_NAME);
+        ser.push_argument_bytes(code);
+        ser.push_argument_bytes(&code_metadata.to_bytes()[..]);
+        UpgradeSerializer(ser)
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+
+    #[inline]
+    pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
+        self.0.push_argument_bytes(bytes);
+    }
+
+    #[inline]
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        self.0.push_arg(arg)
+    }
+}
+
+/// Deserializes from Numbat's contract deploy format.
+///
+/// The code, VM type and code metadata are parsed upfront, the init arguments can then be retrieved one by one.
+pub struct DeployDeserializer<'a> {
+    code: Vec<u8>,
+    vm_type: Vec<u8>,
+    code_metadata: CodeMetadata,
+    args: CallDataDeserializer<'a>,
+}
+
+impl<'a> DeployDeserializer<'a> {
+    pub fn new(source: &'a [u8]) -> Result<Self, SCError> {
+        let mut args = CallDataDeserializer::new(source);
+        let code = args.func_name_from_hex()?;
+        let vm_type = next_required_argument(&mut args)?;
+        let code_metadata = CodeMetadata::from_bytes(next_required_argument(&mut args)?.as_slice())?;
+        Ok(DeployDeserializer {
+            code,
+            vm_type,
+            code_metadata,
+            args,
+        })
+    }
+
+    #[inline]
+    pub fn code(&self) -> &[u8] {
+        self.code.as_slice()
+    }
+
+    #[inline]
+    pub fn vm_type(&self) -> &[u8] {
+        self.vm_type.as_slice()
+    }
+
+    #[inline]
+    pub fn code_metadata(&self) -> CodeMetadata {
+        self.code_metadata
+    }
+
+    /// Deserializer positioned at the first init argument.
+    #[inline]
+    pub fn args(&mut self) -> &mut CallDataDeserializer<'a> {
+        &mut self.args
+    }
+
+    #[inline]
+    pub fn into_args(self) -> CallDataDeserializer<'a> {
+        self.args
+    }
+}
+
+/// Deserializes from Numbat's contract upgrade format.
+///
+/// The code and code metadata are parsed upfront, the init arguments can then be retrieved one by one.
+pub struct UpgradeDeserializer<'a> {
+    code: Vec<u8>,
+    code_metadata: CodeMetadata,
+    args: CallDataDeserializer<'a>,
+}
+
+impl<'a> UpgradeDeserializer<'a> {
+    pub fn new(source: &'a [u8]) -> Result<Self, SCError> {
+        let mut args = CallDataDeserializer::new(source);
+        if args.get_func_name() != UPGRADE_FUNC_NAME {
+            return Err(SCError::Static(err_msg::DESERIALIZATION_NOT_UPGRADE));
+        }
+        let code = next_required_argument(&mut args)?;
+        let code_metadata = CodeMetadata::from_bytes(next_required_argument(&mut args)?.as_slice())?;
+        Ok(UpgradeDeserializer {
+            code,
+            code_metadata,
+            args,
+        })
+    }
+
+    #[inline]
+    pub fn code(&self) -> &[u8] {
+        self.code.as_slice()
+    }
+
+    #[inline]
+    pub fn code_metadata(&self) -> CodeMetadata {
+        self.code_metadata
+    }
+
+    /// Deserializer positioned at the first init argument.
+    #[inline]
+    pub fn args(&mut self) -> &mut CallDataDeserializer<'a> {
+        &mut self.args
+    }
+
+    #[inline]
+    pub fn into_args(self) -> CallDataDeserializer<'a> {
+        self.args
+    }
+}
+
+fn next_required_argument(de: &mut CallDataDeserializer) -> Result<Vec<u8>, SCError> {
+    match de.next_argument()? {
+        Some(arg) => Ok(arg),
+        None => Err(SCError::Static(err_msg::DESERIALIZATION_MISSING_DEPLOY_FIELD)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn test_code_metadata() {
+        let metadata = CodeMetadata::UPGRADEABLE | CodeMetadata::PAYABLE;
+        assert!(metadata.is_upgradeable());
+        assert!(metadata.is_payable());
+        assert!(!metadata.is_readable());
+        assert_eq!(metadata.to_bytes(), [0x01, 0x02]);
+        assert_eq!(CodeMetadata::from_bytes(&[0x05, 0x00]), Ok(CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE));
+        assert!(CodeMetadata::from_bytes(&[0x01]).is_err());
+    }
+
+    #[test]
+    fn test_deploy_round_trip() {
+        let mut ser = DeploySerializer::new(CODE, VM_TYPE_ANDES, CodeMetadata::UPGRADEABLE);
+        ser.push_arg(&0x1234u32).unwrap();
+        ser.push_argument_bytes(&[]);
+        assert_eq!(ser.as_slice(), &b"0061736d01000000@0500@0100@1234@"[..]);
+
+        let data = ser.into_vec();
+        let mut de = DeployDeserializer::new(data.as_slice()).unwrap();
+        assert_eq!(de.code(), CODE);
+        assert_eq!(de.vm_type(), VM_TYPE_ANDES);
+        assert_eq!(de.code_metadata(), CodeMetadata::UPGRADEABLE);
+        assert_eq!(de.args().next_arg::<u32>(), Ok(Some(0x1234)));
+        let mut args = de.into_args();
+        assert_eq!(args.next_argument(), Ok(Some(Vec::new())));
+        assert_eq!(args.next_argument(), Ok(None));
+    }
+
+    #[test]
+    fn test_deploy_no_args() {
+        let ser = DeploySerializer::new(CODE, VM_TYPE_ANDES, CodeMetadata::DEFAULT);
+        let data = ser.into_vec();
+        assert_eq!(data.as_slice(), &b"0061736d01000000@0500@0000"[..]);
+        let mut de = DeployDeserializer::new(data.as_slice()).unwrap();
+        assert_eq!(de.code_metadata(), CodeMetadata::DEFAULT);
+        assert_eq!(de.args().remaining_args::<u32>(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_deploy_invalid() {
+        assert_eq!(DeployDeserializer::new(&b"0061736d01000000@0500"[..]).err(),
+            Some(SCError::Static(err_msg::DESERIALIZATION_MISSING_DEPLOY_FIELD)));
+        assert_eq!(DeployDeserializer::new(&b"0061736d01000000@0500@01"[..]).err(),
+            Some(SCError::Static(err_msg::DESERIALIZATION_BAD_CODE_METADATA)));
+        let odd_code_err = DeployDeserializer::new(&b"0061736d0100000@0500@0100"[..]).err().unwrap();
+        assert!(odd_code_err.as_bytes().starts_with(err_msg::DESERIALIZATION_ODD_DIGITS));
+    }
+
+    #[test]
+    fn test_upgrade_round_trip() {
+        let mut ser = UpgradeSerializer::new(CODE, CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE);
+        ser.push_arg(&5u8).unwrap();
+        ser.push_arg(&6u8).unwrap();
+        assert_eq!(ser.as_slice(), &b"upgradeContract@0061736d01000000@0500@05@06"[..]);
+
+        let data = ser.into_vec();
+        let mut de = UpgradeDeserializer::new(data.as_slice()).unwrap();
+        assert_eq!(de.code(), CODE);
+        assert!(de.code_metadata().is_readable());
+        assert_eq!(de.args().remaining_args::<u8>(), Ok([5, 6].to_vec()));
+    }
+
+    #[test]
+    fn test_upgrade_invalid() {
+        assert_eq!(UpgradeDeserializer::new(&b"func@0061736d01000000@0100"[..]).err(),
+            Some(SCError::Static(err_msg::DESERIALIZATION_NOT_UPGRADE)));
+        assert_eq!(UpgradeDeserializer::new(&b"upgradeContract@0061736d01000000"[..]).err(),
+            Some(SCError::Static(err_msg::DESERIALIZATION_MISSING_DEPLOY_FIELD)));
+    }
+}
@@ -1,5 +1,7 @@
 
 use alloc::vec::Vec;
+use numbat_codec::Encode;
+use crate::io::sc_error::SCError;
 
 use super::SEPARATOR;
 
@@ -11,7 +13,7 @@ fn half_byte_to_hex_digit(num: u8) -> u8 {
     }
 }
 
-fn byte_to_hex(byte: u8) -> (u8, u8) {
+pub(super) fn byte_to_hex(byte: u8) -> (u8, u8) {
     let digit1 = half_byte_to_hex_digit(byte>>4);
     let digit2 = half_byte_to_hex_digit(byte&0x0f);
     (digit1, digit2)
@@ -28,7 +30,7 @@ pub struct CallDataSerializer(Vec<u8>);
 /// 
 /// CallDataSerializer owns its output.
 /// 
-/// Converting from whatever type the argument to bytes is not in scope. Use the `serializer` module for that.
+/// Arguments can be pushed either as raw bytes, or as any type that can be top-encoded via `push_arg`.
 /// 
 impl CallDataSerializer {
     pub fn new(func_name: &[u8]) -> Self {
@@ -41,6 +43,11 @@ impl CallDataSerializer {
         self.0.as_slice()
     }
 
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
     fn push_byte(&mut self, byte: u8) {
         let (digit1, digit2) = byte_to_hex(byte);
         self.0.push(digit1);
@@ -50,10 +57,22 @@ impl CallDataSerializer {
     pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
         self.0.reserve(1 + bytes.len() * 2);
         self.0.push(SEPARATOR);
+        self.push_hex(bytes);
+    }
+
+    /// Appends the hex representation of the bytes, without a separator.
+    pub(super) fn push_hex(&mut self, bytes: &[u8]) {
+        self.0.reserve(bytes.len() * 2);
         for byte in bytes.iter() {
             self.push_byte(*byte);
         }
     }
+
+    /// Top-encodes the argument and appends it.
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        arg.using_top_encoded(|buf| self.push_argument_bytes(buf))
+            .map_err(SCError::PushAsyncEncodeErr)
+    }
 }
 
 #[cfg(test)]
@@ -110,6 +129,15 @@ mod tests {
         assert_eq!(cd.as_slice(), &b"func@ffff@"[..]);
     }
 
+    #[test]
+    fn test_push_arg() {
+        let mut cd = CallDataSerializer::new(&b"func"[..]);
+        cd.push_arg(&0x1234u32).unwrap();
+        cd.push_arg(&0u64).unwrap();
+        cd.push_arg(&(1u8, [2u16].to_vec())).unwrap();
+        assert_eq!(cd.into_vec(), b"func@1234@@01000000010002".to_vec());
+    }
+
     #[test]
     fn test_push_some_empty_2() {
         let mut cd = CallDataSerializer::new(&*b"func");
@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+use alloc::string::String;
+use core::fmt;
+use numbat_codec::{Encode, Decode, DecodeError};
+use crate::io::sc_error::SCError;
+
+use super::SEPARATOR;
+use super::cd_ser::{CallDataSerializer, byte_to_hex};
+use super::cd_de::CallDataDeserializer;
+
+/// A function call in Numbat's smart contract call format, held as a function name and a list of raw arguments.
+///
+/// Unlike `CallDataSerializer` and `CallDataDeserializer`, it can be inspected and modified in any order,
+/// so it is convenient for building calls for proxies or test scenarios, and for checking them afterwards.
+///
+/// `Display` produces the serialized form, e.g. "funcName@0000@aaaa@1234@@".
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct CallData {
+    func_name: Vec<u8>,
+    args: Vec<Vec<u8>>,
+}
+
+impl CallData {
+    pub fn new(func_name: &[u8]) -> Self {
+        CallData {
+            func_name: func_name.to_vec(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Parses serialized call data.
+    pub fn parse(source: &[u8]) -> Result<Self, SCError> {
+        let mut de = CallDataDeserializer::new(source);
+        let mut call_data = CallData::new(de.get_func_name());
+        while let Some(arg) = de.next_argument()? {
+            call_data.args.push(arg);
+        }
+        Ok(call_data)
+    }
+
+    #[inline]
+    pub fn func_name(&self) -> &[u8] {
+        self.func_name.as_slice()
+    }
+
+    /// Raw bytes of all arguments.
+    #[inline]
+    pub fn args(&self) -> &[Vec<u8>] {
+        self.args.as_slice()
+    }
+
+    #[inline]
+    pub fn num_args(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn push_argument_bytes(&mut self, bytes: &[u8]) {
+        self.args.push(bytes.to_vec());
+    }
+
+    /// Top-encodes the argument and appends it.
+    pub fn push_arg<T: Encode>(&mut self, arg: &T) -> Result<(), SCError> {
+        let bytes = arg.top_encode().map_err(SCError::PushAsyncEncodeErr)?;
+        self.args.push(bytes);
+        Ok(())
+    }
+
+    /// Top-decodes the argument at the given index.
+    /// Returns `None` if there are not that many arguments.
+    pub fn arg<T: Decode>(&self, index: usize) -> Option<Result<T, DecodeError>> {
+        self.args.get(index).map(|bytes| numbat_codec::decode_from_byte_slice(bytes.as_slice()))
+    }
+
+    /// Produces the serialized form.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut ser = CallDataSerializer::new(self.func_name.as_slice());
+        for arg in self.args.iter() {
+            ser.push_argument_bytes(arg.as_slice());
+        }
+        ser.into_vec()
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes.iter() {
+        let (digit1, digit2) = byte_to_hex(*byte);
+        write!(f, "{}{}", digit1 as char, digit2 as char)?;
+    }
+    Ok(())
+}
+
+struct HexArg<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for HexArg<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.0)
+    }
+}
+
+impl fmt::Display for CallData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(self.func_name.as_slice()))?;
+        for arg in self.args.iter() {
+            write!(f, "{}", SEPARATOR as char)?;
+            write_hex(f, arg.as_slice())?;
+        }
+        Ok(())
+    }
+}
+
+/// Shows the function name as text and the arguments as hex.
+impl fmt::Debug for CallData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let args: Vec<HexArg> = self.args.iter().map(|arg| HexArg(arg.as_slice())).collect();
+        f.debug_struct("CallData")
+            .field("func_name", &String::from_utf8_lossy(self.func_name.as_slice()))
+            .field("args", &args)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_build_and_serialize() {
+        let mut cd = CallData::new(&b"func"[..]);
+        cd.push_arg(&0x1234u32).unwrap();
+        cd.push_argument_bytes(&[]);
+        cd.push_arg(&(1u8, [2u16].to_vec())).unwrap();
+        assert_eq!(cd.num_args(), 3);
+        assert_eq!(cd.serialize(), b"func@1234@@01000000010002".to_vec());
+        assert_eq!(format!("{}", cd), "func@1234@@01000000010002");
+        assert_eq!(format!("{:?}", cd), r#"CallData { func_name: "func", args: [1234, , 01000000010002] }"#);
+    }
+
+    #[test]
+    fn test_parse() {
+        let cd = CallData::parse(&b"func@1234@@ff"[..]).unwrap();
+        assert_eq!(cd.func_name(), &b"func"[..]);
+        assert_eq!(cd.args(), &[[0x12, 0x34].to_vec(), Vec::new(), [0xff].to_vec()][..]);
+        assert_eq!(cd.arg::<u16>(0), Some(Ok(0x1234)));
+        assert_eq!(cd.arg::<i8>(2), Some(Ok(-1)));
+        assert_eq!(cd.arg::<u8>(3), None);
+        assert_eq!(CallData::parse(cd.serialize().as_slice()), Ok(cd));
+
+        assert_eq!(CallData::parse(&b"func"[..]), Ok(CallData::new(&b"func"[..])));
+        assert!(CallData::parse(&b"func@123"[..]).is_err());
+
+        // the example in the doc comment
+        let cd = CallData::parse(&b"funcName@0000@aaaa@1234@@"[..]).unwrap();
+        assert_eq!(cd.num_args(), 5);
+        assert_eq!(format!("{}", cd), "funcName@0000@aaaa@1234@@");
+    }
+}
@@ -1,8 +1,14 @@
 
 mod cd_ser;
 mod cd_de;
+mod cd_value;
+mod cd_deploy;
+mod cd_builtin;
 
 pub use cd_ser::*;
 pub use cd_de::*;
+pub use cd_value::*;
+pub use cd_deploy::*;
+pub use cd_builtin::*;
 
 const SEPARATOR: u8 = b'@';
@@ -6,23 +6,38 @@ pub const ARG_ASYNC_WRONG_NUMBER: &[u8] = b"wrong number of arguments provided t
 pub const ARG_ASYNC_RETURN_WRONG_NUMBER: &[u8] = b"wrong number of arguments returned by async call";
 pub const ARG_CALLBACK_TOO_FEW:  &[u8] = b"too few callback arguments provided";
 pub const ARG_CALLBACK_TOO_MANY: &[u8] = b"too many callback arguments provided";
+pub const RESULT_WRONG_NUMBER: &[u8] = b"wrong number of results";
 
 pub const ARG_OUT_OF_RANGE: &[u8] = b"argument out of range";
 pub const ARG_BAD_LENGTH: &[u8] = b"argument has wrong length";
 pub const ARG_BAD_LENGTH_32: &[u8] = b"argument has wrong length: 32 bytes expected";
+pub const ARG_EXCEEDS_BUFFER: &[u8] = b"argument does not fit in buffer";
 pub const ARG_DECODE_ERROR_1: &[u8] = b"argument decode error (";
 pub const ARG_DECODE_ERROR_2: &[u8] = b"): ";
 pub const STORAGE_DECODE_ERROR: &[u8] = b"storage decode error: ";
 
 pub const BIG_UINT_EXCEEDS_SLICE: &[u8] = b"big uint as_bytes exceed target slice";
 pub const BIG_UINT_SUB_NEGATIVE: &[u8] = b"cannot subtract because result would be negative";
+pub const BIG_INT_SQRT_NEGATIVE: &[u8] = b"cannot take the square root of a negative number";
 
 pub const DESERIALIZATION_INVALID_BYTE: &[u8] = b"call data deserialization error: not a valid byte";
 pub const DESERIALIZATION_NOT_32_BYTES: &[u8] = b"call data deserialization error: 32 as_bytes expected";
 pub const DESERIALIZATION_ODD_DIGITS: &[u8] = b"call data deserialization error: odd number of digits in hex representation";
 pub const DESERIALIZATION_ARG_OUT_OF_RANGE: &[u8] = b"call data deserialization error: argument out of range";
+pub const DESERIALIZATION_BUFFER_TOO_SMALL: &[u8] = b"call data deserialization error: argument does not fit in buffer";
+pub const DESERIALIZATION_DECODE_ERROR: &[u8] = b"call data deserialization error: ";
+pub const DESERIALIZATION_MISSING_DEPLOY_FIELD: &[u8] = b"call data deserialization error: code, VM type or code metadata missing";
+pub const DESERIALIZATION_BAD_CODE_METADATA: &[u8] = b"call data deserialization error: code metadata must have 2 bytes";
+pub const DESERIALIZATION_NOT_UPGRADE: &[u8] = b"call data deserialization error: not a contract upgrade";
+
+pub const ASYNC_CALL_GAS_LIMIT_NOT_SUPPORTED: &[u8] = b"async call gas limit not supported";
+
+pub const BUILTIN_WRONG_ARGS: &[u8] = b"wrong arguments for built-in function";
+
+pub const VIEW_STATE_CHANGE: &[u8] = b"views cannot change state";
 
 pub const CALLBACK_BAD_FUNC: &[u8] = b"no callback function with that name exists in contract";
+pub const FUNCTION_NOT_FOUND: &[u8] = b"invalid function (not found)";
 
 pub const STORAGE_NOT_I64: &[u8] = b"storage not i64";
 pub const STORAGE_NOT_32_BYTES: &[u8] = b"32 bytes of data expected in storage at key";
@@ -29,14 +29,7 @@ where
             Ok(Some(arg_bytes)) => {
                 match numbat_codec::decode_from_byte_slice(arg_bytes.as_slice()) {
                     Ok(v) => Ok(Some(v)),
-                    Err(de_err) => {
-                        let mut decode_err_message: Vec<u8> = Vec::new();
-                        decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_1);
-                        decode_err_message.extend_from_slice(arg_id);
-                        decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_2);
-                        decode_err_message.extend_from_slice(de_err.message_bytes());
-                        Err(SCError::Dynamic(decode_err_message))
-                    }
+                    Err(de_err) => Err(arg_decode_error(arg_id, de_err)),
                 }
             },
             Ok(None) => Ok(None),
@@ -45,3 +38,23 @@ where
     }
 }
 
+
+impl<'a> DynArgBorrowedLoader for CallDataArgLoader<'a> {
+    #[inline]
+    fn next_arg_len(&self) -> usize {
+        self.deser.next_argument_len()
+    }
+
+    fn next_arg_borrowed<'b, T: DecodeBorrowed<'b>>(&mut self, buffer: &'b mut [u8], arg_id: ArgId) -> Result<Option<T>, SCError> {
+        match self.deser.next_argument_to_slice(buffer) {
+            Ok(Some(arg_bytes)) => {
+                match numbat_codec::decode_borrowed_from_byte_slice(arg_bytes) {
+                    Ok(v) => Ok(Some(v)),
+                    Err(de_err) => Err(arg_decode_error(arg_id, de_err)),
+                }
+            },
+            Ok(None) => Ok(None),
+            Err(sc_err) => Err(sc_err)
+        }
+    }
+}
@@ -16,6 +16,8 @@ where
             // performing a forceful cast
             let big_uint_arg = api.get_argument_big_uint(index);
             let cast_big_uint: T = unsafe { core::mem::transmute_copy(&big_uint_arg) };
+            // the copy now owns the value, which must not be dropped twice
+            core::mem::forget(big_uint_arg);
             cast_big_uint
         },
         TypeInfo::I64 => {
@@ -38,18 +40,34 @@ where
             match numbat_codec::decode_from_byte_slice(arg_bytes.as_slice()) {
                 Ok(v) => v,
                 Err(de_err) => {
-                    let mut decode_err_message: Vec<u8> = Vec::new();
-                    decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_1);
-                    decode_err_message.extend_from_slice(arg_id);
-                    decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_2);
-                    decode_err_message.extend_from_slice(de_err.message_bytes());
-                    api.signal_error(decode_err_message.as_slice())
+                    let sc_err = arg_decode_error(arg_id, de_err);
+                    api.signal_error(sc_err.as_bytes())
                 }
             }
         }
     }
 }
 
+/// Loads an argument that borrows from the buffer, like `&[u8]` or `&str`.
+/// The buffer is resized to fit the argument, which is then copied only once, from the VM.
+pub fn load_single_arg_borrowed<'b, A, BigInt, BigUint, T>(api: &A, index: i32, buffer: &'b mut Vec<u8>, arg_id: ArgId) -> T
+where
+    T: DecodeBorrowed<'b>,
+    BigUint: BigUintApi + 'static,
+    BigInt: BigIntApi<BigUint> + 'static,
+    A: ContractIOApi<BigInt, BigUint> + 'static
+{
+    buffer.resize(api.get_argument_len(index), 0u8);
+    api.copy_argument_to_slice(index, buffer.as_mut_slice());
+    match numbat_codec::decode_borrowed_from_byte_slice(buffer.as_slice()) {
+        Ok(v) => v,
+        Err(de_err) => {
+            let sc_err = arg_decode_error(arg_id, de_err);
+            api.signal_error(sc_err.as_bytes())
+        }
+    }
+}
+
 pub struct DynEndpointArgLoader<'a, A, BigInt, BigUint>
 where
     BigUint: BigUintApi + 'static,
@@ -102,3 +120,37 @@ where
         }
     }
 }
+
+impl<'a, A, BigInt, BigUint> DynArgBorrowedLoader for DynEndpointArgLoader<'a, A, BigInt, BigUint>
+where
+    BigUint: BigUintApi + 'static,
+    BigInt: BigIntApi<BigUint> + 'static,
+    A: ContractIOApi<BigInt, BigUint> + 'static
+{
+    fn next_arg_len(&self) -> usize {
+        if self.current_index >= self.num_arguments {
+            0
+        } else {
+            self.api.get_argument_len(self.current_index)
+        }
+    }
+
+    fn next_arg_borrowed<'b, T: DecodeBorrowed<'b>>(&mut self, buffer: &'b mut [u8], arg_id: ArgId) -> Result<Option<T>, SCError> {
+        if self.current_index >= self.num_arguments {
+            return Ok(None);
+        }
+
+        let arg_len = self.api.get_argument_len(self.current_index);
+        if arg_len > buffer.len() {
+            return Err(SCError::Static(err_msg::ARG_EXCEEDS_BUFFER));
+        }
+        let arg_slice = &mut buffer[..arg_len];
+        self.api.copy_argument_to_slice(self.current_index, arg_slice);
+        self.current_index += 1;
+
+        match numbat_codec::decode_borrowed_from_byte_slice(arg_slice) {
+            Ok(v) => Ok(Some(v)),
+            Err(de_err) => Err(arg_decode_error(arg_id, de_err)),
+        }
+    }
+}
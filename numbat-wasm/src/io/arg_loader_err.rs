@@ -1,4 +1,5 @@
 use crate::*;
+use numbat_codec::DecodeError;
 
 use core::marker::PhantomData;
 
@@ -6,6 +7,17 @@ pub trait DynArgErrHandler {
     fn handle_sc_error(&self, err: SCError) -> !;
 }
 
+/// Builds the error message for an argument that could not be decoded.
+/// Includes the path to the failing component, for structured arguments.
+pub fn arg_decode_error(arg_id: ArgId, de_err: DecodeError) -> SCError {
+    let mut decode_err_message: Vec<u8> = Vec::new();
+    decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_1);
+    decode_err_message.extend_from_slice(arg_id);
+    decode_err_message.extend_from_slice(err_msg::ARG_DECODE_ERROR_2);
+    de_err.append_message_to(&mut decode_err_message);
+    SCError::Dynamic(decode_err_message)
+}
+
 // TODO: split ContractIOApi and maybe we won't need this struct anymore
 pub struct DynEndpointErrHandler<'a, A, BigInt, BigUint>
 where
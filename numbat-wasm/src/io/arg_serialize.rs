@@ -16,9 +16,7 @@ where
 {
     #[inline]
     fn push_async_arg(&self, serializer: &mut CallDataSerializer) -> Result<(), SCError> {
-        self
-            .using_top_encoded(|buf| serializer.push_argument_bytes(buf))
-            .map_err(SCError::PushAsyncEncodeErr)
+        serializer.push_arg(self)
     }
 }
 
@@ -14,6 +14,16 @@ pub trait DynArgLoader<T>: Sized {
     fn next_arg(&mut self, arg_id: ArgId) -> Result<Option<T>, SCError>;
 }
 
+/// Loader that can also hand out arguments borrowing from a caller-provided buffer,
+/// so that byte slices and strings can be loaded without allocating.
+pub trait DynArgBorrowedLoader {
+    /// Length of the next argument, so that callers can provide a buffer that fits it.
+    /// 0 if there are no more arguments.
+    fn next_arg_len(&self) -> usize;
+
+    fn next_arg_borrowed<'b, T: DecodeBorrowed<'b>>(&mut self, buffer: &'b mut [u8], arg_id: ArgId) -> Result<Option<T>, SCError>;
+}
+
 pub trait ArgType<D>: Sized {
     fn load(loader: &mut D, arg_id: ArgId) -> Result<Self, SCError>;
 }
@@ -30,6 +40,26 @@ where
     }
 }
 
+#[inline]
+pub fn load_dyn_arg_borrowed<'b, T, D, E>(loader: &mut D, buffer: &'b mut [u8], err_handler: &E, arg_id: ArgId) -> T
+where
+    T: DecodeBorrowed<'b>,
+    D: DynArgBorrowedLoader,
+    E: DynArgErrHandler,
+{
+    match loader.next_arg_borrowed(buffer, arg_id) {
+        Ok(Some(arg)) => arg,
+        Ok(None) => err_handler.handle_sc_error(SCError::Static(err_msg::ARG_WRONG_NUMBER)),
+        Err(sc_err) => err_handler.handle_sc_error(sc_err),
+    }
+}
+
+/// Buffer that fits the next argument exactly, for `load_dyn_arg_borrowed`.
+#[inline]
+pub fn next_arg_buffer<D: DynArgBorrowedLoader>(loader: &D) -> Vec<u8> {
+    alloc::vec![0u8; loader.next_arg_len()]
+}
+
 #[inline]
 pub fn check_no_more_args<D, E>(loader: &D, err_handler: &E)
 where
@@ -116,6 +146,13 @@ impl<T> VarArgs<T> {
 
 }
 
+/// Each item is a separate argument or result.
+impl<T: TypeDescription> TypeDescription for VarArgs<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::MultiValue(Box::new(T::type_descriptor()))
+    }
+}
+
 impl<T, D> ArgType<D> for VarArgs<T>
 where
     T: ArgType<D>,
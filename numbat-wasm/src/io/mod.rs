@@ -5,6 +5,7 @@ pub mod arg_loader_cd;
 pub mod arg_loader_err;
 pub mod arg_serialize;
 pub mod finish;
+pub mod result_decode;
 pub mod sc_error;
 
 pub use arg_types::*;
@@ -14,6 +15,7 @@ pub use arg_loader_cd::*;
 pub use arg_loader_err::*;
 pub use arg_serialize::*;
 pub use finish::*;
+pub use result_decode::*;
 pub use sc_error::*;
 
 #[cfg(test)]
@@ -42,6 +44,35 @@ pub mod test_arg_load {
         assert!(!DynArgLoader::<()>::has_next(&cd_loader));
     }
 
+    #[test]
+    fn test_borrowed_args() {
+        let input: &[u8] = b"func@0102@616263@05";
+        let de = CallDataDeserializer::new(input);
+        let mut cd_loader = CallDataArgLoader::new(de);
+        let mut buffer1 = next_arg_buffer(&cd_loader);
+        assert_eq!(buffer1.len(), 2);
+        let mut buffer2 = [0u8; 8];
+        let mut buffer3 = [0u8; 8];
+        let arg1: &[u8] = load_dyn_arg_borrowed(&mut cd_loader, &mut buffer1, &PanickingDynArgErrHandler, &[]);
+        let arg2: &str = load_dyn_arg_borrowed(&mut cd_loader, &mut buffer2, &PanickingDynArgErrHandler, &[]);
+        let arg3: u32 = load_dyn_arg_borrowed(&mut cd_loader, &mut buffer3, &PanickingDynArgErrHandler, &[]);
+        assert_eq!(arg1, &[1, 2][..]);
+        assert_eq!(arg2, "abc");
+        assert_eq!(arg3, 5);
+        assert_eq!(next_arg_buffer(&cd_loader).len(), 0);
+        assert!(!DynArgLoader::<()>::has_next(&cd_loader));
+    }
+
+    #[test]
+    fn test_borrowed_arg_decode_error() {
+        let input: &[u8] = b"func@ff";
+        let de = CallDataDeserializer::new(input);
+        let mut cd_loader = CallDataArgLoader::new(de);
+        let mut buffer = [0u8; 8];
+        let result: Result<Option<&str>, SCError> = cd_loader.next_arg_borrowed(&mut buffer, &b"s"[..]);
+        assert_eq!(result, Err(SCError::Dynamic(b"argument decode error (s): invalid value".to_vec())));
+    }
+
     #[test]
     fn test_simple_vec_arg() {
         let input: &[u8] = b"some_other_func@000000020000000300000006";
@@ -52,6 +83,15 @@ pub mod test_arg_load {
         assert!(!DynArgLoader::<()>::has_next(&cd_loader));
     }
 
+    #[test]
+    fn test_arg_decode_error_path() {
+        let input: &[u8] = b"func@0000000200000003000000";
+        let de = CallDataDeserializer::new(input);
+        let mut cd_loader = CallDataArgLoader::new(de);
+        let result: Result<Option<Vec<u32>>, SCError> = cd_loader.next_arg(&b"v"[..]);
+        assert_eq!(result, Err(SCError::Dynamic(b"argument decode error (v): [2]: input too short".to_vec())));
+    }
+
     #[test]
     fn test_var_args() {
         let input: &[u8] = b"func@1111@2222";
@@ -64,6 +104,13 @@ pub mod test_arg_load {
         assert_eq!(arg_vec[1], 0x2222i32);
     }
 
+    #[test]
+    fn test_var_args_type_descriptor() {
+        use numbat_codec::{TypeDescription, TypeDescriptor};
+        assert_eq!(VarArgs::<Queue<u32>>::type_descriptor(),
+            TypeDescriptor::MultiValue(Box::new(TypeDescriptor::List(Box::new(TypeDescriptor::U32)))));
+    }
+
     #[test]
     fn test_multi_arg_2() {
         let input: &[u8] = b"func@1111@2222";
@@ -157,4 +204,17 @@ pub mod test_arg_load {
             }
         }
     }
+
+    #[test]
+    fn test_decode_endpoint_results() {
+        let results = [[0x11u8].to_vec(), [0x22u8].to_vec(), [0x33u8].to_vec()].to_vec();
+        let decoded = decode_endpoint_results::<SCResult<MultiResult2<u8, MultiResultVec<u8>>>>(results);
+        assert_eq!(decoded, Ok((0x11u8, [0x22u8, 0x33u8].to_vec())));
+
+        let decoded = decode_endpoint_results::<OptionalResult<u32>>(Vec::new());
+        assert_eq!(decoded, Ok(None));
+
+        let decoded = decode_endpoint_results::<u8>([[1u8].to_vec(), [2u8].to_vec()].to_vec());
+        assert_eq!(decoded, Err(SCError::Static(err_msg::RESULT_WRONG_NUMBER)));
+    }
 }
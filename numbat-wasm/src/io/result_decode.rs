@@ -0,0 +1,155 @@
+use crate::*;
+use numbat_codec::*;
+
+const RESULT_ARG_ID: ArgId = b"result";
+
+/// Loads the values returned by an endpoint, one by one.
+/// Used on the host side, to interpret the results of calls made in tests.
+pub struct ResultArgLoader {
+    results: Vec<Vec<u8>>,
+    next_index: usize,
+}
+
+impl ResultArgLoader {
+    pub fn new(results: Vec<Vec<u8>>) -> Self {
+        ResultArgLoader {
+            results,
+            next_index: 0,
+        }
+    }
+}
+
+impl<T> DynArgLoader<T> for ResultArgLoader
+where
+    T: Decode,
+{
+    #[inline]
+    fn has_next(&self) -> bool {
+        self.next_index < self.results.len()
+    }
+
+    fn next_arg(&mut self, arg_id: ArgId) -> Result<Option<T>, SCError> {
+        match self.results.get(self.next_index) {
+            Some(result_bytes) => {
+                self.next_index += 1;
+                match numbat_codec::decode_from_byte_slice(result_bytes.as_slice()) {
+                    Ok(v) => Ok(Some(v)),
+                    Err(de_err) => Err(arg_decode_error(arg_id, de_err)),
+                }
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// The host side counterpart of `EndpointResult`:
+/// rebuilds the value an endpoint returned from the results it finished with.
+/// Errors are not part of the results, so `SCResult<T>` decodes to whatever `T` decodes to.
+pub trait EndpointResultDecode: Sized {
+    type Decoded;
+
+    fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError>;
+}
+
+impl<T> EndpointResultDecode for T
+where
+    T: Decode,
+{
+    type Decoded = T;
+
+    fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError> {
+        T::load(loader, RESULT_ARG_ID)
+    }
+}
+
+impl<T> EndpointResultDecode for SCResult<T>
+where
+    T: EndpointResultDecode,
+{
+    type Decoded = T::Decoded;
+
+    #[inline]
+    fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError> {
+        T::decode_results(loader)
+    }
+}
+
+impl<T> EndpointResultDecode for MultiResultVec<T>
+where
+    T: EndpointResultDecode,
+{
+    type Decoded = Vec<T::Decoded>;
+
+    fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError> {
+        let mut result_vec: Vec<T::Decoded> = Vec::new();
+        while DynArgLoader::<()>::has_next(loader) {
+            result_vec.push(T::decode_results(loader)?);
+        }
+        Ok(result_vec)
+    }
+}
+
+impl<T> EndpointResultDecode for OptionalResult<T>
+where
+    T: EndpointResultDecode,
+{
+    type Decoded = Option<T::Decoded>;
+
+    fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError> {
+        if DynArgLoader::<()>::has_next(loader) {
+            Ok(Some(T::decode_results(loader)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+macro_rules! multi_result_decode_impls {
+    ($(($mr:ident $($n:tt $name:ident)+) )+) => {
+        $(
+            impl<$($name),+> EndpointResultDecode for $mr<$($name,)+>
+            where
+                $($name: EndpointResultDecode,)+
+            {
+                type Decoded = ($($name::Decoded,)+);
+
+                fn decode_results(loader: &mut ResultArgLoader) -> Result<Self::Decoded, SCError> {
+                    Ok((
+                        $(
+                            $name::decode_results(loader)?,
+                        )+
+                    ))
+                }
+            }
+        )+
+    }
+}
+
+multi_result_decode_impls! {
+    (MultiResult1  0 T0)
+    (MultiResult2  0 T0 1 T1)
+    (MultiResult3  0 T0 1 T1 2 T2)
+    (MultiResult4  0 T0 1 T1 2 T2 3 T3)
+    (MultiResult5  0 T0 1 T1 2 T2 3 T3 4 T4)
+    (MultiResult6  0 T0 1 T1 2 T2 3 T3 4 T4 5 T5)
+    (MultiResult7  0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6)
+    (MultiResult8  0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7)
+    (MultiResult9  0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8)
+    (MultiResult10 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9)
+    (MultiResult11 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10)
+    (MultiResult12 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11)
+    (MultiResult13 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12)
+    (MultiResult14 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13)
+    (MultiResult15 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14)
+    (MultiResult16 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
+}
+
+/// Decodes all results of an endpoint call, failing if any are left over.
+pub fn decode_endpoint_results<R: EndpointResultDecode>(results: Vec<Vec<u8>>) -> Result<R::Decoded, SCError> {
+    let mut loader = ResultArgLoader::new(results);
+    let decoded = R::decode_results(&mut loader)?;
+    if DynArgLoader::<()>::has_next(&loader) {
+        return Err(SCError::Static(err_msg::RESULT_WRONG_NUMBER));
+    }
+    Ok(decoded)
+}
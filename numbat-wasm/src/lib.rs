@@ -11,16 +11,20 @@ pub use numbat_codec;
 mod types;
 pub mod io;
 mod proxy;
+pub mod async_call;
+mod read_only_api;
 pub mod storage;
 pub mod err_msg;
 pub mod call_data;
 
 pub use types::*;
 pub use io::*;
-pub use storage::{storage_get, storage_set, BorrowedMutStorage};
+pub use storage::{storage_get, storage_set, storage_get_opt, storage_set_opt, storage_is_empty, storage_clear, BorrowedMutStorage};
 pub use finish::SCResult;
 pub use call_data::*;
 pub use proxy::OtherContractHandle;
+pub use async_call::{AsyncCall, CallbackClosure};
+pub use read_only_api::ReadOnlyApi;
 
 use core::ops::{Add, Sub, Mul, Div, Rem, Neg};
 use core::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
@@ -84,6 +88,21 @@ where
 
     fn async_call(&self, to: &Address, amount: &BigUint, data: &[u8]);
 
+    /// Async call that can spend at most `gas_limit`.
+    /// Andes gives async calls all the remaining gas and has no way to limit it,
+    /// so there the call fails instead of ignoring the limit.
+    fn async_call_with_gas(&self, to: &Address, amount: &BigUint, data: &[u8], gas_limit: u64);
+
+    /// Number of async calls made so far in the current transaction.
+    /// Andes stops execution at the first async call, so only one async call per transaction is supported there,
+    /// and this is always 0. The debugger mock supports several.
+    fn get_num_async_calls(&self) -> u32;
+
+    /// When executing a callback, the index of the async call that it answers,
+    /// among the async calls of the original transaction.
+    /// Always 0 on Andes, see `get_num_async_calls`.
+    fn get_callback_call_index(&self) -> u32;
+
     fn get_tx_hash(&self) -> H256;
 
     fn get_gas_left(&self) -> i64;
@@ -210,6 +229,8 @@ pub trait ContractIOApi<BigInt, BigUint> {
 /// 
 /// Since most values in smart contracts will not be signed, as well as for safety,
 /// most of the functionality if provided for unsigned integers.
+///
+/// `min` and `max` come with `Ord`.
 pub trait BigUintApi: 
     Sized +
     From<u64> +
@@ -244,6 +265,7 @@ pub trait BigUintApi:
     PartialOrd<u64> +
     numbat_codec::Encode +
     numbat_codec::Decode +
+    numbat_codec::TypeDescription +
 {
     fn zero() -> Self {
         0u64.into()
@@ -260,6 +282,41 @@ pub trait BigUintApi:
     fn to_bytes_be_pad_right(&self, nr_bytes: usize) -> Option<Vec<u8>>;
 
     fn from_bytes_be(bytes: &[u8]) -> Self;
+
+    fn pow(&self, exp: u32) -> Self;
+
+    /// Square root, rounded down.
+    fn sqrt(&self) -> Self;
+
+    /// Base 2 logarithm, rounded down. `None` for zero.
+    fn log2(&self) -> Option<u32>;
+
+    /// Subtraction that returns `None` instead of signalling an error when the result would be negative.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// `None` if the value does not fit.
+    fn to_u64(&self) -> Option<u64>;
+
+    /// Computes `self * mul / div`, rounded as required.
+    /// Division by zero is an error, as with the `/` operator.
+    fn mul_div(&self, mul: &Self, div: &Self, rounding: Rounding) -> Self {
+        let product = self.clone() * mul.clone();
+        let quotient = product.clone() / div.clone();
+        let remainder = product % div.clone();
+        if remainder == 0u64 {
+            return quotient;
+        }
+        let round_up = match rounding {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::Nearest => remainder.clone() + remainder >= *div,
+        };
+        if round_up {
+            quotient + Self::from(1u32)
+        } else {
+            quotient
+        }
+    }
 }
 
 // BigInt sign.
@@ -269,6 +326,17 @@ pub enum Sign {
     Plus,
 }
 
+/// How `mul_div` rounds results that are not exact.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    /// Towards negative infinity.
+    Down,
+    /// Towards positive infinity.
+    Up,
+    /// To the nearest integer, halves away from zero.
+    Nearest,
+}
+
 /// Definition of the BigInt type required by the API.
 pub trait BigIntApi<BigUint>: 
         Sized +
@@ -286,7 +354,7 @@ pub trait BigIntApi<BigUint>:
         DivAssign +
         Rem<Output=Self> +
         RemAssign +
-        Neg +
+        Neg<Output=Self> +
         PartialEq<Self> +
         Eq +
         PartialOrd<Self> +
@@ -295,6 +363,7 @@ pub trait BigIntApi<BigUint>:
         PartialOrd<i64> +
         numbat_codec::Encode +
         numbat_codec::Decode +
+        numbat_codec::TypeDescription +
 {
     fn zero() -> Self {
         0i64.into()
@@ -307,15 +376,91 @@ pub trait BigIntApi<BigUint>:
     fn to_signed_bytes_be(&self) -> Vec<u8>;
 
     fn from_signed_bytes_be(bytes: &[u8]) -> Self;
+
+    fn pow(&self, exp: u32) -> Self;
+
+    /// Square root, rounded down. Negative numbers are an error.
+    fn sqrt(&self) -> Self;
+
+    /// Base 2 logarithm, rounded down. `None` for zero and negative numbers.
+    fn log2(&self) -> Option<u32>;
+
+    /// `None` if the value does not fit.
+    fn to_i64(&self) -> Option<i64>;
+
+    /// Computes `self * mul / div`, rounded as required.
+    /// Division by zero is an error, as with the `/` operator.
+    fn mul_div(&self, mul: &Self, div: &Self, rounding: Rounding) -> Self {
+        let product = self.clone() * mul.clone();
+        // `/` truncates towards zero, so the remainder has the sign of the product
+        let quotient = product.clone() / div.clone();
+        let remainder = product % div.clone();
+        if remainder == 0i64 {
+            return quotient;
+        }
+        let positive_quotient = (remainder > 0i64) == (*div > 0i64);
+        let away_from_zero = match rounding {
+            Rounding::Down => !positive_quotient,
+            Rounding::Up => positive_quotient,
+            Rounding::Nearest => {
+                let double_remainder = remainder.clone() + remainder;
+                let double_remainder_abs = if double_remainder < 0i64 { -double_remainder } else { double_remainder };
+                let div_abs = if *div < 0i64 { -div.clone() } else { div.clone() };
+                double_remainder_abs >= div_abs
+            },
+        };
+        match (away_from_zero, positive_quotient) {
+            (false, _) => quotient,
+            (true, true) => quotient + Self::from(1i32),
+            (true, false) => quotient - Self::from(1i32),
+        }
+    }
 }
 
 /// CallableContract is the means by which the debugger calls methods in the contract.
 pub trait CallableContract {
     fn call(&self, fn_name: &'static str);
 
+    /// Views only read state, so they can be queried without a transaction.
+    fn is_view(&self, fn_name: &str) -> bool;
+
     fn clone_contract(&self) -> Box<dyn CallableContract>;
 }
 
+/// Executes the endpoint calls made by the test clients generated for contracts in debug mode.
+/// Implemented by the debugger mocks, so contracts do not need to depend on them.
+pub trait EndpointCaller {
+    type BigInt: BigIntApi<Self::BigUint> + 'static;
+    type BigUint: BigUintApi + 'static;
+
+    /// Calls an endpoint in a transaction.
+    /// Returns the raw results, or the error message if the call failed, in which case no state is changed.
+    fn call_endpoint(&self,
+        caller: &Address,
+        contract_address: &Address,
+        call_value: Self::BigUint,
+        endpoint_name: &'static str,
+        args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Vec<u8>>;
+
+    /// Deploys a contract, calling its init with the given value and arguments.
+    /// Returns the address of the new contract, or the error message.
+    fn deploy_contract(&self,
+        deployer: &Address,
+        contract: Box<dyn CallableContract>,
+        call_value: Self::BigUint,
+        args: Vec<Vec<u8>>) -> Result<Address, Vec<u8>>;
+}
+
+/// Splits serialized call data back into its arguments.
+pub fn call_data_args(call_data: &[u8]) -> Result<Vec<Vec<u8>>, SCError> {
+    let mut de = CallDataDeserializer::new(call_data);
+    let mut args = Vec::new();
+    while let Some(arg) = de.next_argument()? {
+        args.push(arg);
+    }
+    Ok(args)
+}
+
 /// Handy way of casting to a contract proxy trait.
 /// Would make more sense to be in numbat-wasm-derive, but Rust "cannot export macro_rules! macros from a `proc-macro` crate type currently".
 #[macro_export]
@@ -329,10 +474,10 @@ macro_rules! contract_proxy {
 #[macro_export]
 macro_rules! imports {
     () => {
-        use numbat_wasm::{Box, Vec, String, Queue, VarArgs, BorrowedMutStorage};
+        use numbat_wasm::{Box, Vec, String, Queue, VarArgs, BorrowedMutStorage, FixedPoint};
         use numbat_wasm::{SCError, SCResult, SCResult::Ok, SCResult::Err};
         use numbat_wasm::{H256, Address, ErrorMessage};
-        use numbat_wasm::{ContractHookApi, ContractIOApi, BigIntApi, BigUintApi, OtherContractHandle, AsyncCallResult, AsyncCallError};
+        use numbat_wasm::{ContractHookApi, ContractIOApi, BigIntApi, BigUintApi, Rounding, OtherContractHandle, AsyncCallResult, AsyncCallError};
         use numbat_wasm::numbat_codec::{Encode, Decode, DecodeError};
         use numbat_wasm::io::*;
         use numbat_wasm::err_msg;
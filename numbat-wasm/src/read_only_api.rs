@@ -0,0 +1,243 @@
+use crate::*;
+
+/// Wraps the API given to view endpoints.
+///
+/// Everything that reads state passes through,
+/// everything that would change it (storage writes, transfers, async calls, logs) signals an error instead.
+#[derive(Clone)]
+pub struct ReadOnlyApi<A>(A);
+
+impl<A> ReadOnlyApi<A> {
+    #[inline]
+    pub fn new(api: A) -> Self {
+        ReadOnlyApi(api)
+    }
+}
+
+impl<A, BigInt, BigUint> ContractHookApi<BigInt, BigUint> for ReadOnlyApi<A>
+where
+    BigInt: numbat_codec::Encode + 'static,
+    BigUint: numbat_codec::Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint> + ContractIOApi<BigInt, BigUint>,
+{
+    #[inline]
+    fn get_sc_address(&self) -> Address {
+        self.0.get_sc_address()
+    }
+
+    #[inline]
+    fn get_owner_address(&self) -> Address {
+        self.0.get_owner_address()
+    }
+
+    #[inline]
+    fn get_caller(&self) -> Address {
+        self.0.get_caller()
+    }
+
+    #[inline]
+    fn get_balance(&self, address: &Address) -> BigUint {
+        self.0.get_balance(address)
+    }
+
+    fn storage_store(&self, _key: &[u8], _value: &[u8]) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn storage_load(&self, key: &[u8]) -> Vec<u8> {
+        self.0.storage_load(key)
+    }
+
+    #[inline]
+    fn storage_load_len(&self, key: &[u8]) -> usize {
+        self.0.storage_load_len(key)
+    }
+
+    fn storage_store_bytes32(&self, _key: &[u8], _value: &[u8; 32]) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn storage_load_bytes32(&self, key: &[u8]) -> [u8; 32] {
+        self.0.storage_load_bytes32(key)
+    }
+
+    fn storage_store_big_uint(&self, _key: &[u8], _value: &BigUint) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn storage_load_big_uint(&self, key: &[u8]) -> BigUint {
+        self.0.storage_load_big_uint(key)
+    }
+
+    fn storage_store_big_int(&self, _key: &[u8], _value: &BigInt) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn storage_load_big_int(&self, key: &[u8]) -> BigInt {
+        self.0.storage_load_big_int(key)
+    }
+
+    fn storage_store_i64(&self, _key: &[u8], _value: i64) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn storage_load_i64(&self, key: &[u8]) -> Option<i64> {
+        self.0.storage_load_i64(key)
+    }
+
+    #[inline]
+    fn get_call_value_big_uint(&self) -> BigUint {
+        self.0.get_call_value_big_uint()
+    }
+
+    fn send_tx(&self, _to: &Address, _amount: &BigUint, _message: &str) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    fn async_call(&self, _to: &Address, _amount: &BigUint, _data: &[u8]) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    fn async_call_with_gas(&self, _to: &Address, _amount: &BigUint, _data: &[u8], _gas_limit: u64) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+
+    #[inline]
+    fn get_num_async_calls(&self) -> u32 {
+        self.0.get_num_async_calls()
+    }
+
+    #[inline]
+    fn get_callback_call_index(&self) -> u32 {
+        self.0.get_callback_call_index()
+    }
+
+    #[inline]
+    fn get_tx_hash(&self) -> H256 {
+        self.0.get_tx_hash()
+    }
+
+    #[inline]
+    fn get_gas_left(&self) -> i64 {
+        self.0.get_gas_left()
+    }
+
+    #[inline]
+    fn get_block_timestamp(&self) -> u64 {
+        self.0.get_block_timestamp()
+    }
+
+    #[inline]
+    fn get_block_nonce(&self) -> u64 {
+        self.0.get_block_nonce()
+    }
+
+    #[inline]
+    fn get_block_round(&self) -> u64 {
+        self.0.get_block_round()
+    }
+
+    #[inline]
+    fn get_block_epoch(&self) -> u64 {
+        self.0.get_block_epoch()
+    }
+
+    #[inline]
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        self.0.sha256(data)
+    }
+
+    #[inline]
+    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
+        self.0.keccak256(data)
+    }
+}
+
+impl<A, BigInt, BigUint> ContractIOApi<BigInt, BigUint> for ReadOnlyApi<A>
+where
+    A: ContractIOApi<BigInt, BigUint>,
+{
+    #[inline]
+    fn get_num_arguments(&self) -> i32 {
+        self.0.get_num_arguments()
+    }
+
+    #[inline]
+    fn check_not_payable(&self) {
+        self.0.check_not_payable()
+    }
+
+    #[inline]
+    fn get_argument_len(&self, arg_index: i32) -> usize {
+        self.0.get_argument_len(arg_index)
+    }
+
+    #[inline]
+    fn copy_argument_to_slice(&self, arg_index: i32, slice: &mut [u8]) {
+        self.0.copy_argument_to_slice(arg_index, slice)
+    }
+
+    #[inline]
+    fn get_argument_vec(&self, arg_index: i32) -> Vec<u8> {
+        self.0.get_argument_vec(arg_index)
+    }
+
+    #[inline]
+    fn get_argument_bytes32(&self, arg_index: i32) -> [u8; 32] {
+        self.0.get_argument_bytes32(arg_index)
+    }
+
+    #[inline]
+    fn get_argument_big_int(&self, arg_id: i32) -> BigInt {
+        self.0.get_argument_big_int(arg_id)
+    }
+
+    #[inline]
+    fn get_argument_big_uint(&self, arg_id: i32) -> BigUint {
+        self.0.get_argument_big_uint(arg_id)
+    }
+
+    #[inline]
+    fn get_argument_i64(&self, arg_id: i32) -> i64 {
+        self.0.get_argument_i64(arg_id)
+    }
+
+    #[inline]
+    fn finish_slice_u8(&self, slice: &[u8]) {
+        self.0.finish_slice_u8(slice)
+    }
+
+    #[inline]
+    fn finish_bytes32(&self, bytes: &[u8; 32]) {
+        self.0.finish_bytes32(bytes)
+    }
+
+    #[inline]
+    fn finish_big_int(&self, b: &BigInt) {
+        self.0.finish_big_int(b)
+    }
+
+    #[inline]
+    fn finish_big_uint(&self, b: &BigUint) {
+        self.0.finish_big_uint(b)
+    }
+
+    #[inline]
+    fn finish_i64(&self, value: i64) {
+        self.0.finish_i64(value)
+    }
+
+    #[inline]
+    fn signal_error(&self, message: &[u8]) -> ! {
+        self.0.signal_error(message)
+    }
+
+    fn write_log(&self, _topics: &[[u8;32]], _data: &[u8]) {
+        self.0.signal_error(err_msg::VIEW_STATE_CHANGE)
+    }
+}
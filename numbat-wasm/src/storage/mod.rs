@@ -1,6 +1,8 @@
 pub mod storage_util;
 pub mod mut_storage;
 pub mod protected_keys;
+pub mod storage_key_check;
 
 pub use storage_util::*;
 pub use mut_storage::*;
+pub use storage_key_check::*;
@@ -0,0 +1,304 @@
+/// Storage identifier of a storage accessor, and whether the accessor appends arguments to it to build keys.
+pub type StorageKeyDecl = (&'static str, bool);
+
+/// All the storage keys of a contract or module, including those of the modules it includes.
+///
+/// Contracts and modules get a generated constant of this type, named like their implementation struct,
+/// so that storage declared in modules can be checked against the contract at compile time.
+/// The endpoint names are listed too, since module endpoints are exported next to those of the contract.
+pub struct StorageKeys {
+    pub own: &'static [StorageKeyDecl],
+    /// Names of the endpoints and views.
+    pub endpoints: &'static [&'static str],
+    /// The included modules, each with its storage prefix.
+    pub modules: &'static [(&'static str, &'static StorageKeys)],
+}
+
+/// The storage prefix of a module instance, which is the prefix of the module that includes it,
+/// followed by the one it was given there.
+#[derive(Clone, Copy)]
+struct KeyPrefix<'a> {
+    parent: Option<&'a KeyPrefix<'a>>,
+    segment: &'a str,
+}
+
+impl<'a> KeyPrefix<'a> {
+    const fn root(segment: &'a str) -> Self {
+        KeyPrefix { parent: None, segment }
+    }
+
+    const fn len(&self) -> usize {
+        match self.parent {
+            Some(parent) => parent.len() + self.segment.len(),
+            None => self.segment.len(),
+        }
+    }
+
+    const fn byte_at(&self, i: usize) -> u8 {
+        let parent_len = match self.parent {
+            Some(parent) => {
+                let parent_len = parent.len();
+                if i < parent_len {
+                    return parent.byte_at(i);
+                }
+                parent_len
+            },
+            None => 0,
+        };
+        self.segment.as_bytes()[i - parent_len]
+    }
+}
+
+/// Keys built from the two declarations can be identical if one identifier is a proper prefix of the other
+/// and arguments get appended to the shorter one, e.g. `"user"` with an address argument and `"userBalance"`.
+///
+/// Each identifier comes after a storage prefix, empty except for module instances that have one.
+pub const fn storage_keys_can_collide(a_prefix: &str, a: StorageKeyDecl, b_prefix: &str, b: StorageKeyDecl) -> bool {
+    keys_can_collide(&KeyPrefix::root(a_prefix), a, &KeyPrefix::root(b_prefix), b)
+}
+
+/// Between the contract and its modules, using the same key is also a collision,
+/// since they would overwrite each other's values.
+pub const fn component_storage_keys_collide(a_prefix: &str, a: StorageKeyDecl, b_prefix: &str, b: StorageKeyDecl) -> bool {
+    component_keys_collide(&KeyPrefix::root(a_prefix), a, &KeyPrefix::root(b_prefix), b)
+}
+
+/// Checks a key of the contract against all the keys of a module instance, nested modules included.
+pub const fn storage_key_collides_with_any(key: StorageKeyDecl, others_prefix: &str, others: &StorageKeys) -> bool {
+    key_collides_with_any(&KeyPrefix::root(""), key, &KeyPrefix::root(others_prefix), others)
+}
+
+/// Checks the keys of two module instances against each other, nested modules included.
+pub const fn storage_key_sets_collide(a_prefix: &str, a: &StorageKeys, b_prefix: &str, b: &StorageKeys) -> bool {
+    key_sets_collide(&KeyPrefix::root(a_prefix), a, &KeyPrefix::root(b_prefix), b)
+}
+
+/// Checks an endpoint name of the contract against all the endpoint names of a module, nested modules included.
+pub const fn endpoint_name_used_by_any(name: &str, others: &StorageKeys) -> bool {
+    let mut i = 0;
+    while i < others.endpoints.len() {
+        if str_eq(name, others.endpoints[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < others.modules.len() {
+        if endpoint_name_used_by_any(name, others.modules[i].1) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Checks the endpoint names of two modules against each other, nested modules included.
+pub const fn endpoint_name_sets_overlap(a: &StorageKeys, b: &StorageKeys) -> bool {
+    let mut i = 0;
+    while i < a.endpoints.len() {
+        if endpoint_name_used_by_any(a.endpoints[i], b) {
+            return true;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < a.modules.len() {
+        if endpoint_name_sets_overlap(a.modules[i].1, b) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Module endpoints are exported on their own, without the storage prefix of the module instance,
+/// so modules that have any, nested modules included, cannot be given a prefix.
+pub const fn has_endpoints(keys: &StorageKeys) -> bool {
+    if !keys.endpoints.is_empty() {
+        return true;
+    }
+    let mut i = 0;
+    while i < keys.modules.len() {
+        if has_endpoints(keys.modules[i].1) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn keys_can_collide(a_prefix: &KeyPrefix, a: StorageKeyDecl, b_prefix: &KeyPrefix, b: StorageKeyDecl) -> bool {
+    (a.1 && is_proper_prefix(a_prefix, a.0, b_prefix, b.0)) ||
+    (b.1 && is_proper_prefix(b_prefix, b.0, a_prefix, a.0))
+}
+
+const fn component_keys_collide(a_prefix: &KeyPrefix, a: StorageKeyDecl, b_prefix: &KeyPrefix, b: StorageKeyDecl) -> bool {
+    (a.1 == b.1 && is_same(a_prefix, a.0, b_prefix, b.0)) ||
+    keys_can_collide(a_prefix, a, b_prefix, b)
+}
+
+const fn key_collides_with_any(key_prefix: &KeyPrefix, key: StorageKeyDecl, others_prefix: &KeyPrefix, others: &StorageKeys) -> bool {
+    let mut i = 0;
+    while i < others.own.len() {
+        if component_keys_collide(key_prefix, key, others_prefix, others.own[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < others.modules.len() {
+        let (segment, module) = others.modules[i];
+        let module_prefix = KeyPrefix { parent: Some(others_prefix), segment };
+        if key_collides_with_any(key_prefix, key, &module_prefix, module) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn key_sets_collide(a_prefix: &KeyPrefix, a: &StorageKeys, b_prefix: &KeyPrefix, b: &StorageKeys) -> bool {
+    let mut i = 0;
+    while i < a.own.len() {
+        if key_collides_with_any(a_prefix, a.own[i], b_prefix, b) {
+            return true;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < a.modules.len() {
+        let (segment, module) = a.modules[i];
+        let module_prefix = KeyPrefix { parent: Some(a_prefix), segment };
+        if key_sets_collide(&module_prefix, module, b_prefix, b) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Byte `i` of `prefix` followed by `identifier`.
+const fn byte_at(prefix: &KeyPrefix, identifier: &[u8], i: usize) -> u8 {
+    let prefix_len = prefix.len();
+    if i < prefix_len {
+        prefix.byte_at(i)
+    } else {
+        identifier[i - prefix_len]
+    }
+}
+
+const fn starts_with(a_prefix: &KeyPrefix, a: &[u8], b_prefix: &KeyPrefix, b: &[u8], len: usize) -> bool {
+    let mut i = 0;
+    while i < len {
+        if byte_at(a_prefix, a, i) != byte_at(b_prefix, b, i) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn is_proper_prefix(a_prefix: &KeyPrefix, a: &str, b_prefix: &KeyPrefix, b: &str) -> bool {
+    let a_len = a_prefix.len() + a.len();
+    a_len < b_prefix.len() + b.len() &&
+        starts_with(a_prefix, a.as_bytes(), b_prefix, b.as_bytes(), a_len)
+}
+
+const fn is_same(a_prefix: &KeyPrefix, a: &str, b_prefix: &KeyPrefix, b: &str) -> bool {
+    let a_len = a_prefix.len() + a.len();
+    a_len == b_prefix.len() + b.len() &&
+        starts_with(a_prefix, a.as_bytes(), b_prefix, b.as_bytes(), a_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_keys_can_collide() {
+        assert!(storage_keys_can_collide("", ("user", true), "", ("userBalance", false)));
+        assert!(storage_keys_can_collide("", ("userBalance", true), "", ("user", true)));
+        assert!(!storage_keys_can_collide("", ("user", false), "", ("userBalance", true)));
+        assert!(!storage_keys_can_collide("", ("user", true), "", ("user", true)));
+        assert!(!storage_keys_can_collide("", ("user", true), "", ("owner", true)));
+
+        assert!(storage_keys_can_collide("", ("us", true), "u", ("serBalance", false)));
+        assert!(!storage_keys_can_collide("", ("user", true), "fee_", ("userBalance", false)));
+    }
+
+    #[test]
+    fn test_component_storage_keys_collide() {
+        assert!(component_storage_keys_collide("", ("owner", false), "", ("owner", false)));
+        assert!(!component_storage_keys_collide("", ("owner", false), "", ("owner", true)));
+        assert!(!component_storage_keys_collide("", ("owner", false), "fee_", ("owner", false)));
+        assert!(component_storage_keys_collide("fee_", ("owner", false), "fee_", ("owner", false)));
+        assert!(component_storage_keys_collide("", ("fee_owner", false), "fee_", ("owner", false)));
+    }
+
+    #[test]
+    fn test_storage_key_sets_collide() {
+        const CONTRACT: StorageKeys = StorageKeys { own: &[("owner", false), ("user", true)], endpoints: &[], modules: &[] };
+        const MODULE: StorageKeys = StorageKeys { own: &[("userCount", false)], endpoints: &[], modules: &[] };
+        const OTHER_MODULE: StorageKeys = StorageKeys { own: &[("fee", false)], endpoints: &[], modules: &[] };
+        assert!(storage_key_sets_collide("", &CONTRACT, "", &MODULE));
+        assert!(storage_key_sets_collide("", &MODULE, "", &CONTRACT));
+        assert!(!storage_key_sets_collide("", &CONTRACT, "", &OTHER_MODULE));
+        assert!(!storage_key_collides_with_any(("owner", false), "", &MODULE));
+        assert!(storage_key_collides_with_any(("user", true), "", &MODULE));
+        assert!(!storage_key_collides_with_any(("user", true), "fees.", &MODULE));
+        assert!(storage_key_sets_collide("", &OTHER_MODULE, "", &OTHER_MODULE));
+        assert!(!storage_key_sets_collide("a.", &OTHER_MODULE, "b.", &OTHER_MODULE));
+    }
+
+    #[test]
+    fn test_nested_module_storage_keys_collide() {
+        const INNER: StorageKeys = StorageKeys { own: &[("count", false)], endpoints: &[], modules: &[] };
+        const OUTER: StorageKeys = StorageKeys { own: &[("fee", false)], endpoints: &[], modules: &[("cnt.", &INNER)] };
+        const OTHER: StorageKeys = StorageKeys { own: &[("x", false)], endpoints: &[], modules: &[("in.", &OUTER)] };
+        assert!(storage_key_collides_with_any(("cnt.count", false), "", &OUTER));
+        assert!(storage_key_collides_with_any(("cnt.", true), "", &OUTER));
+        assert!(!storage_key_collides_with_any(("count", false), "", &OUTER));
+        assert!(storage_key_collides_with_any(("a.cnt.count", false), "a.", &OUTER));
+        assert!(storage_key_collides_with_any(("in.cnt.count", false), "", &OTHER));
+        assert!(storage_key_collides_with_any(("o.in.cnt", true), "o.", &OTHER));
+        assert!(!storage_key_collides_with_any(("o.in.cnt.count", true), "o.", &OTHER));
+        assert!(storage_key_sets_collide("x.in.cnt.", &INNER, "x.", &OTHER));
+        assert!(storage_key_sets_collide("in.cnt.", &INNER, "", &OTHER));
+        assert!(!storage_key_sets_collide("a.", &OTHER, "b.", &OTHER));
+    }
+
+    #[test]
+    fn test_endpoint_names_overlap() {
+        const INNER: StorageKeys = StorageKeys { own: &[], endpoints: &["getCount"], modules: &[] };
+        const OUTER: StorageKeys = StorageKeys { own: &[], endpoints: &["getFee"], modules: &[("cnt.", &INNER)] };
+        const OTHER: StorageKeys = StorageKeys { own: &[], endpoints: &["getFees", "getCoun"], modules: &[] };
+        assert!(endpoint_name_used_by_any("getFee", &OUTER));
+        assert!(endpoint_name_used_by_any("getCount", &OUTER));
+        assert!(!endpoint_name_used_by_any("getFees", &OUTER));
+        assert!(!endpoint_name_used_by_any("getCount", &OTHER));
+        assert!(endpoint_name_sets_overlap(&OUTER, &INNER));
+        assert!(endpoint_name_sets_overlap(&INNER, &OUTER));
+        assert!(!endpoint_name_sets_overlap(&OUTER, &OTHER));
+
+        const EMPTY: StorageKeys = StorageKeys { own: &[("count", false)], endpoints: &[], modules: &[] };
+        const WRAPPER: StorageKeys = StorageKeys { own: &[], endpoints: &[], modules: &[("in.", &INNER)] };
+        assert!(has_endpoints(&INNER));
+        assert!(has_endpoints(&WRAPPER));
+        assert!(!has_endpoints(&EMPTY));
+    }
+}
@@ -71,6 +71,8 @@ where
             // performing a forceful cast
             let big_uint_value = api.storage_load_big_uint(key);
             let cast_big_uint: T = unsafe { core::mem::transmute_copy(&big_uint_value) };
+            // the copy now owns the value, which must not be dropped twice
+            core::mem::forget(big_uint_value);
             cast_big_uint
         },
         TypeInfo::I64 | TypeInfo::U64 => {
@@ -128,10 +130,61 @@ where
                 Err(de_err) => {
                     let mut decode_err_message: Vec<u8> = Vec::new();
                     decode_err_message.extend_from_slice(err_msg::STORAGE_DECODE_ERROR);
-                    decode_err_message.extend_from_slice(de_err.message_bytes());
+                    de_err.append_message_to(&mut decode_err_message);
                     api.signal_error(decode_err_message.as_slice())
                 },
             }
         }
     }
 }
+
+/// Stores the value with a leading presence byte, so that `storage_get_opt` can tell it apart from a missing one,
+/// even when the value itself encodes as no bytes at all, e.g. zero numbers.
+#[inline]
+pub fn storage_set_opt<'a, 'k, A, BigInt, BigUint, T>(api: &'a A, key: &'k [u8], value: &T)
+where
+    'a: 'k,
+    T: Encode,
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint> + ContractIOApi<BigInt, BigUint> + 'a
+{
+    storage_set(api, key, &Some(value));
+}
+
+/// Reads a value stored with `storage_set_opt`, or `None` if nothing was stored under the key.
+/// Values stored with `storage_set` lack the presence byte and do not decode this way.
+#[inline]
+pub fn storage_get_opt<'a, 'k, A, BigInt, BigUint, T>(api: &'a A, key: &'k [u8]) -> Option<T>
+where
+    'a: 'k,
+    T: Decode,
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint> + ContractIOApi<BigInt, BigUint> + 'a
+{
+    storage_get(api, key)
+}
+
+#[inline]
+pub fn storage_is_empty<'a, 'k, A, BigInt, BigUint>(api: &'a A, key: &'k [u8]) -> bool
+where
+    'a: 'k,
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint> + 'a
+{
+    api.storage_load_len(key) == 0
+}
+
+/// Removes the value stored under the key. Storing an empty value is how the protocol deletes entries.
+#[inline]
+pub fn storage_clear<'a, 'k, A, BigInt, BigUint>(api: &'a A, key: &'k [u8])
+where
+    'a: 'k,
+    BigInt: Encode + 'static,
+    BigUint: Encode + 'static,
+    A: ContractHookApi<BigInt, BigUint> + 'a
+{
+    api.storage_store(key, &[]);
+}
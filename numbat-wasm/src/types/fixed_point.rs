@@ -0,0 +1,133 @@
+use crate::{BigUintApi, Rounding};
+use core::ops::{Add, Sub};
+use numbat_codec::*;
+
+/// Unsigned decimal number with `DECIMALS` fractional digits.
+///
+/// It is held as the underlying integer, scaled by 10^`DECIMALS`: with 18 decimals, 1.5 is 1500000000000000000.
+/// Encoding, storage and endpoint arguments/results also use this integer,
+/// so a `FixedPoint` can replace a plain BigUint amount without changing the format.
+///
+/// Addition and subtraction are exact and available as operators.
+/// Multiplication, division and rescaling to fewer decimals need to be told how to round.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct FixedPoint<BigUint, const DECIMALS: u32> {
+    raw: BigUint,
+}
+
+impl<BigUint, const DECIMALS: u32> FixedPoint<BigUint, DECIMALS>
+where
+    BigUint: BigUintApi,
+{
+    /// Wraps an integer that is already scaled.
+    #[inline]
+    pub fn from_raw(raw: BigUint) -> Self {
+        FixedPoint { raw }
+    }
+
+    #[inline]
+    pub fn raw(&self) -> &BigUint {
+        &self.raw
+    }
+
+    #[inline]
+    pub fn into_raw(self) -> BigUint {
+        self.raw
+    }
+
+    /// 10^`DECIMALS`, the raw value of 1.
+    pub fn scale() -> BigUint {
+        BigUint::from(10u32).pow(DECIMALS)
+    }
+
+    pub fn zero() -> Self {
+        FixedPoint::from_raw(BigUint::zero())
+    }
+
+    pub fn from_integer(value: BigUint) -> Self {
+        FixedPoint::from_raw(value * Self::scale())
+    }
+
+    /// Drops the fractional part, rounding as required.
+    pub fn to_integer(&self, rounding: Rounding) -> BigUint {
+        self.raw.mul_div(&BigUint::from(1u32), &Self::scale(), rounding)
+    }
+
+    /// Subtraction that returns `None` instead of signalling an error when the result would be negative.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.raw.checked_sub(&other.raw).map(FixedPoint::from_raw)
+    }
+
+    pub fn mul(&self, other: &Self, rounding: Rounding) -> Self {
+        FixedPoint::from_raw(self.raw.mul_div(&other.raw, &Self::scale(), rounding))
+    }
+
+    /// Division by zero is an error, as with the `/` operator.
+    pub fn div(&self, other: &Self, rounding: Rounding) -> Self {
+        FixedPoint::from_raw(self.raw.mul_div(&Self::scale(), &other.raw, rounding))
+    }
+
+    /// Converts to another number of decimals.
+    /// Rounding only matters when going to fewer decimals.
+    pub fn rescale<const NEW_DECIMALS: u32>(&self, rounding: Rounding) -> FixedPoint<BigUint, NEW_DECIMALS> {
+        let raw = if NEW_DECIMALS >= DECIMALS {
+            self.raw.clone() * BigUint::from(10u32).pow(NEW_DECIMALS - DECIMALS)
+        } else {
+            self.raw.mul_div(&BigUint::from(1u32), &BigUint::from(10u32).pow(DECIMALS - NEW_DECIMALS), rounding)
+        };
+        FixedPoint::from_raw(raw)
+    }
+}
+
+impl<BigUint, const DECIMALS: u32> Add for FixedPoint<BigUint, DECIMALS>
+where
+    BigUint: BigUintApi,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        FixedPoint::from_raw(self.raw + other.raw)
+    }
+}
+
+/// Signals an error if the result would be negative, like BigUint subtraction.
+impl<BigUint, const DECIMALS: u32> Sub for FixedPoint<BigUint, DECIMALS>
+where
+    BigUint: BigUintApi,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        FixedPoint::from_raw(self.raw - other.raw)
+    }
+}
+
+impl<BigUint: Encode, const DECIMALS: u32> Encode for FixedPoint<BigUint, DECIMALS> {
+    #[inline]
+    fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
+        self.raw.dep_encode_to(dest)
+    }
+
+    #[inline]
+    fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
+        self.raw.using_top_encoded(f)
+    }
+}
+
+impl<BigUint: TypeDescription, const DECIMALS: u32> TypeDescription for FixedPoint<BigUint, DECIMALS> {
+    fn type_descriptor() -> TypeDescriptor {
+        BigUint::type_descriptor()
+    }
+}
+
+impl<BigUint: Decode, const DECIMALS: u32> Decode for FixedPoint<BigUint, DECIMALS> {
+    #[inline]
+    fn top_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+        Ok(FixedPoint { raw: BigUint::top_decode(input)? })
+    }
+
+    #[inline]
+    fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
+        Ok(FixedPoint { raw: BigUint::dep_decode(input)? })
+    }
+}
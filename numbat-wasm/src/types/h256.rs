@@ -126,12 +126,21 @@ impl H256 {
 use numbat_codec::*;
 
 impl Encode for H256 {
+    const DEP_ENCODED_LEN: Option<usize> = Some(32);
+
     fn dep_encode_to<O: Output>(&self, dest: &mut O) -> Result<(), EncodeError> {
         dest.write(&self.0[..]);
         Ok(())
     }
 }
 
+/// Described as its underlying 32 byte array, since it encodes identically.
+impl TypeDescription for H256 {
+    fn type_descriptor() -> TypeDescriptor {
+        <[u8; 32]>::type_descriptor()
+    }
+}
+
 impl Decode for H256 {
     fn dep_decode<I: Input>(input: &mut I) -> Result<Self, DecodeError> {
         let mut arr = [0u8; 32];
@@ -152,6 +161,18 @@ mod esd_light_tests {
         ser_deser_ok(addr, &[4u8; 32]);
     }
 
+    #[test]
+    fn test_address_type_descriptor() {
+        assert_eq!(Address::type_descriptor(), TypeDescriptor::Array(Box::new(TypeDescriptor::U8), 32));
+    }
+
+    #[test]
+    fn test_address_encoded_len() {
+        let addr = Address::from([4u8; 32]);
+        assert_eq!(addr.dep_encoded_len(), 32);
+        assert_eq!(<(Address, u32)>::DEP_ENCODED_LEN, Some(36));
+    }
+
     #[test]
     fn test_opt_address() {
         let addr = Address::from([4u8; 32]);
@@ -1,5 +1,7 @@
 pub mod h256;
 pub mod queue;
+pub mod fixed_point;
 
 pub use h256::*;
 pub use queue::*;
+pub use fixed_point::*;
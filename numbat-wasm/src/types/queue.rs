@@ -90,12 +90,24 @@ impl<T: Encode> Encode for Queue<T> {
         self.as_slice().dep_encode_to(dest)
 	}
 
+	#[inline]
+	fn dep_encoded_len(&self) -> usize {
+        self.as_slice().dep_encoded_len()
+	}
+
 	#[inline]
 	fn using_top_encoded<F: FnOnce(&[u8])>(&self, f: F) -> Result<(), EncodeError> {
         self.as_slice().using_top_encoded(f)
 	}
 }
 
+/// Described like a Vec.
+impl<T: TypeDescription> TypeDescription for Queue<T> {
+    fn type_descriptor() -> TypeDescriptor {
+        Vec::<T>::type_descriptor()
+    }
+}
+
 /// Deserializes like a Vec.
 impl<T: Decode> Decode for Queue<T> {
 	#[inline]